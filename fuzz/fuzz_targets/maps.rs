@@ -0,0 +1,26 @@
+#![no_main]
+
+use std::collections::{BTreeMap, HashMap};
+
+use libfuzzer_sys::fuzz_target;
+use map_to_javascript_html::{verify_script_safe, MapToJavaScriptHTML};
+
+fuzz_target!(|entries: Vec<(String, String)>| {
+    let b_tree_map: BTreeMap<&str, &str> =
+        entries.iter().map(|(key, value)| (key.as_str(), value.as_str())).collect();
+    let hash_map: HashMap<&str, &str> =
+        b_tree_map.iter().map(|(key, value)| (*key, *value)).collect();
+
+    let keys: Vec<&str> = entries.iter().map(|(key, _)| key.as_str()).collect();
+
+    for script in [
+        b_tree_map.to_javascript_html("text"),
+        b_tree_map.to_javascript_html_with_keys("text", &keys),
+        hash_map.to_javascript_html("text"),
+        hash_map.to_javascript_html_with_keys("text", &keys),
+    ] {
+        if let Err(violation) = verify_script_safe(script.as_bytes(), "text") {
+            panic!("{}: {:?}", violation, script);
+        }
+    }
+});
@@ -0,0 +1,49 @@
+#![no_main]
+
+use std::collections::BTreeMap;
+
+use libfuzzer_sys::fuzz_target;
+use map_to_javascript_html::{
+    verify_script_safe, PlaceholderSyntax, Redaction, Renderer, UnsafeKeyPolicy,
+};
+
+fuzz_target!(|input: (Vec<(String, String)>, u8)| {
+    let (entries, options) = input;
+
+    let map: BTreeMap<String, String> = entries.into_iter().collect();
+
+    let mut renderer = Renderer::new();
+
+    renderer = match options & 0b11 {
+        1 => renderer.placeholders(PlaceholderSyntax::Braces),
+        2 => renderer.placeholders(PlaceholderSyntax::Printf),
+        3 => renderer.placeholders(PlaceholderSyntax::Delimiters {
+            open:  String::from("${"),
+            close: String::from("}"),
+        }),
+        _ => renderer,
+    };
+
+    // the output modes which `verify_script_safe` does not accept, such as immutability, are run in Boa by `tests/engine.rs`
+    if options & 0b100 != 0 {
+        renderer = renderer.ascii_only(true);
+    }
+
+    if options & 0b1000 != 0 {
+        renderer = renderer.max_value_length(usize::from(options >> 6));
+    }
+
+    if options & 0b1_0000 != 0 {
+        renderer = renderer.unsafe_keys(UnsafeKeyPolicy::NullPrototype);
+    }
+
+    if options & 0b10_0000 != 0 {
+        renderer = renderer.redact(Redaction::sensitive());
+    }
+
+    if let Ok(script) = renderer.render(&map, "text") {
+        if let Err(violation) = verify_script_safe(script.as_bytes(), "text") {
+            panic!("{}: {:?}", violation, script);
+        }
+    }
+});
@@ -0,0 +1,31 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use map_to_javascript_html::{verify_script_safe, MapToJavaScriptHTML};
+
+fuzz_target!(|data: &[u8]| {
+    let text = match std::str::from_utf8(data) {
+        Ok(text) => text,
+        Err(_) => return,
+    };
+
+    let mut scripts = Vec::new();
+
+    if let Ok(map) = serde_json::from_str::<serde_json::Map<String, serde_json::Value>>(text) {
+        scripts.push(map.to_javascript_html("text"));
+    }
+
+    if let Ok(map) = toml::from_str::<toml::Table>(text) {
+        scripts.push(map.to_javascript_html("text"));
+    }
+
+    if let Ok(map) = serde_yaml::from_str::<serde_yaml::Mapping>(text) {
+        scripts.push(map.to_javascript_html("text"));
+    }
+
+    for script in scripts {
+        if let Err(violation) = verify_script_safe(script.as_bytes(), "text") {
+            panic!("{}: {:?}", violation, script);
+        }
+    }
+});
@@ -0,0 +1,87 @@
+use core::fmt::{self, Display, Formatter};
+use std::{error::Error, io, path::PathBuf};
+
+use crate::ParseError;
+
+/// Errors which can occur when loading a `Catalog` from a directory.
+#[derive(Debug)]
+pub enum CatalogError {
+    IOError(io::Error),
+    /// A `.properties`, `.po` or `.ftl` file has a syntax error.
+    ParseError {
+        path:  PathBuf,
+        error: ParseError,
+    },
+    #[cfg(feature = "serde_json")]
+    JSONError {
+        path:  PathBuf,
+        error: serde_json::Error,
+    },
+    #[cfg(feature = "toml")]
+    TOMLError {
+        path:  PathBuf,
+        error: toml::de::Error,
+    },
+    #[cfg(feature = "serde_yaml")]
+    YAMLError {
+        path:  PathBuf,
+        error: serde_yaml::Error,
+    },
+    /// The root of a JSON or YAML file is not a map.
+    NotAMap {
+        path: PathBuf,
+    },
+    /// A directory cannot be watched.
+    #[cfg(feature = "notify")]
+    WatchError(notify::Error),
+}
+
+impl From<io::Error> for CatalogError {
+    #[inline]
+    fn from(error: io::Error) -> Self {
+        CatalogError::IOError(error)
+    }
+}
+
+#[cfg(feature = "notify")]
+impl From<notify::Error> for CatalogError {
+    #[inline]
+    fn from(error: notify::Error) -> Self {
+        CatalogError::WatchError(error)
+    }
+}
+
+impl Display for CatalogError {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            CatalogError::IOError(error) => Display::fmt(error, f),
+            CatalogError::ParseError {
+                path,
+                error,
+            } => write!(f, "{}: {}", path.display(), error),
+            #[cfg(feature = "serde_json")]
+            CatalogError::JSONError {
+                path,
+                error,
+            } => write!(f, "{}: {}", path.display(), error),
+            #[cfg(feature = "toml")]
+            CatalogError::TOMLError {
+                path,
+                error,
+            } => write!(f, "{}: {}", path.display(), error),
+            #[cfg(feature = "serde_yaml")]
+            CatalogError::YAMLError {
+                path,
+                error,
+            } => write!(f, "{}: {}", path.display(), error),
+            CatalogError::NotAMap {
+                path,
+            } => write!(f, "{}: the root is not a map", path.display()),
+            #[cfg(feature = "notify")]
+            CatalogError::WatchError(error) => Display::fmt(error, f),
+        }
+    }
+}
+
+impl Error for CatalogError {}
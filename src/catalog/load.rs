@@ -0,0 +1,123 @@
+use std::{fs, path::Path};
+
+use super::{Catalog, CatalogError};
+use crate::{parse_po, parse_properties};
+
+impl Catalog {
+    /// Load the files in a directory as locales. The locale of a file is its name without the extension, such as `en-GB` of `en-GB.properties`.
+    ///
+    /// `.properties` and `.po` files are always supported. `.json` files need the `serde` feature, `.toml` files need the `toml` feature, `.yaml` and `.yml` files need the `yaml` feature, and `.ftl` files need the `fluent` feature, whose messages are compiled into functions. Other files and subdirectories are ignored.
+    pub fn load_dir<P: AsRef<Path>, L: Into<String>>(
+        path: P,
+        default_locale: L,
+    ) -> Result<Catalog, CatalogError> {
+        let mut catalog = Catalog::new(default_locale);
+
+        let mut paths = Vec::new();
+
+        for entry in fs::read_dir(path)? {
+            let entry = entry?;
+
+            if entry.file_type()?.is_file() {
+                paths.push(entry.path());
+            }
+        }
+
+        paths.sort();
+
+        for path in paths {
+            catalog.load_file(path)?;
+        }
+
+        Ok(catalog)
+    }
+
+    /// Load a file as a locale, whose name is the file name without the extension. The entries are merged into the locale if it already exists. Return `Ok(false)` if the type of the file is not supported.
+    pub fn load_file<P: AsRef<Path>>(&mut self, path: P) -> Result<bool, CatalogError> {
+        let path = path.as_ref();
+
+        let (locale, extension) = match (path.file_stem(), path.extension()) {
+            (Some(locale), Some(extension)) => match (locale.to_str(), extension.to_str()) {
+                (Some(locale), Some(extension)) => (locale, extension.to_ascii_lowercase()),
+                _ => return Ok(false),
+            },
+            _ => return Ok(false),
+        };
+
+        let parse_error = |error| CatalogError::ParseError {
+            path: path.to_path_buf(),
+            error,
+        };
+
+        match extension.as_str() {
+            "properties" => {
+                let map = parse_properties(&fs::read_to_string(path)?).map_err(parse_error)?;
+
+                self.insert_locale(locale, &map);
+            },
+            "po" => {
+                let map = parse_po(&fs::read_to_string(path)?).map_err(parse_error)?;
+
+                self.insert_locale(locale, &map);
+            },
+            #[cfg(feature = "fluent-syntax")]
+            "ftl" => {
+                let messages = crate::FluentMessages::parse(
+                    &fs::read_to_string(path)?,
+                    locale,
+                    crate::FluentOutput::Functions,
+                )
+                .map_err(parse_error)?;
+
+                self.insert_locale(locale, &messages);
+            },
+            #[cfg(feature = "serde_json")]
+            "json" => {
+                let value: serde_json::Value = serde_json::from_str(&fs::read_to_string(path)?)
+                    .map_err(|error| CatalogError::JSONError {
+                        path: path.to_path_buf(),
+                        error,
+                    })?;
+
+                match value {
+                    serde_json::Value::Object(map) => self.insert_locale(locale, &map),
+                    _ => {
+                        return Err(CatalogError::NotAMap {
+                            path: path.to_path_buf()
+                        })
+                    },
+                }
+            },
+            #[cfg(feature = "toml")]
+            "toml" => {
+                let table: toml::Table =
+                    fs::read_to_string(path)?.parse().map_err(|error| CatalogError::TOMLError {
+                        path: path.to_path_buf(),
+                        error,
+                    })?;
+
+                self.insert_locale(locale, &table);
+            },
+            #[cfg(feature = "serde_yaml")]
+            "yaml" | "yml" => {
+                let value: serde_yaml::Value = serde_yaml::from_str(&fs::read_to_string(path)?)
+                    .map_err(|error| CatalogError::YAMLError {
+                        path: path.to_path_buf(),
+                        error,
+                    })?;
+
+                match value {
+                    serde_yaml::Value::Mapping(mapping) => self.insert_locale(locale, &mapping),
+                    _ => {
+                        return Err(CatalogError::NotAMap {
+                            path: path.to_path_buf()
+                        })
+                    },
+                }
+            },
+            _ => return Ok(false),
+        }
+
+        Ok(true)
+    }
+}
@@ -0,0 +1,316 @@
+#[cfg(feature = "std")]
+mod error;
+#[cfg(feature = "std")]
+mod load;
+mod negotiation;
+#[cfg(feature = "notify")]
+mod watch;
+
+use alloc::{borrow::Cow, boxed::Box, collections::BTreeMap, string::String, vec::Vec};
+use core::{borrow::Borrow, fmt::Display, hash::Hash};
+#[cfg(feature = "std")]
+use std::io::{self, Write};
+
+#[cfg(feature = "std")]
+pub use error::*;
+pub use negotiation::*;
+#[cfg(feature = "notify")]
+pub use watch::*;
+
+use crate::{renderer::write_entry_to_vec, JavaScriptEntries, JsValue, MapToJavaScriptHTML};
+
+type Entries = BTreeMap<String, JsValue<'static>>;
+
+/// Check whether two locales are the same, ignoring the case and the difference between `-` and `_`.
+fn locale_eq(a: &str, b: &str) -> bool {
+    a.len() == b.len()
+        && a.bytes().zip(b.bytes()).all(|(a, b)| {
+            a.eq_ignore_ascii_case(&b) || (matches!(a, b'-' | b'_') && matches!(b, b'-' | b'_'))
+        })
+}
+
+/// Maps of multiple locales. The value of a key is resolved through a fallback chain, such as `fr-CA` → `fr` → `en`, where the last locale is the default locale of the catalog.
+///
+/// ```
+/// use std::collections::BTreeMap;
+///
+/// use map_to_javascript_html::{Catalog, MapToJavaScriptHTML};
+///
+/// let mut en = BTreeMap::new();
+/// en.insert("hello", "Hello!");
+/// en.insert("bye", "Bye!");
+///
+/// let mut fr = BTreeMap::new();
+/// fr.insert("hello", "Bonjour !");
+///
+/// let mut catalog = Catalog::new("en");
+///
+/// catalog.insert_locale("en", &en);
+/// catalog.insert_locale("fr", &fr);
+///
+/// assert_eq!(
+///     "_text['bye']='Bye!';_text['hello']='Bonjour !';",
+///     catalog.localize("fr-CA").to_javascript_html("_text")
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct Catalog {
+    default_locale: String,
+    locales:        Vec<(String, Entries)>,
+}
+
+impl Catalog {
+    /// Create an empty catalog. `default_locale` is the last locale of every fallback chain.
+    #[inline]
+    pub fn new<L: Into<String>>(default_locale: L) -> Catalog {
+        Catalog {
+            default_locale: default_locale.into(), locales: Vec::new()
+        }
+    }
+
+    /// The default locale.
+    #[inline]
+    pub fn default_locale(&self) -> &str {
+        self.default_locale.as_str()
+    }
+
+    /// The available locales, in the order they were inserted.
+    #[inline]
+    pub fn locales(&self) -> impl Iterator<Item = &str> {
+        self.locales.iter().map(|(locale, _)| locale.as_str())
+    }
+
+    /// Insert the entries of a map as a locale. If the locale already exists, the entries are merged into it and the existing keys are replaced.
+    pub fn insert_locale<L: Into<String>, M: ?Sized + JavaScriptEntries>(
+        &mut self,
+        locale: L,
+        map: &M,
+    ) {
+        let locale = locale.into();
+
+        let index = match self.locales.iter().position(|(l, _)| locale_eq(l, &locale)) {
+            Some(index) => index,
+            None => {
+                self.locales.push((locale, BTreeMap::new()));
+
+                self.locales.len() - 1
+            },
+        };
+
+        let entries = &mut self.locales[index].1;
+
+        for (key, value) in map.javascript_entries() {
+            entries.insert(key.into_owned(), value.into_owned());
+        }
+    }
+
+    #[inline]
+    fn find_locale(&self, locale: &str) -> Option<&(String, Entries)> {
+        self.locales.iter().find(|(l, _)| locale_eq(l, locale))
+    }
+
+    /// The available locales used to resolve the keys for a locale, from the most specific one to the default locale. The subtags of the locale are removed one by one, so `zh-Hant-TW` falls back to `zh-Hant` and `zh`.
+    pub fn fallback_chain(&self, locale: &str) -> Vec<&str> {
+        let available: Vec<&str> = self.locales().collect();
+
+        negotiation::fallback_chain(locale, &available, &self.default_locale)
+    }
+
+    /// Get a view of this catalog in which every key is resolved through the fallback chain of a locale. The view can be written by the `MapToJavaScriptHTML` trait or a `Renderer`.
+    pub fn localize(&self, locale: &str) -> LocalizedCatalog<'_> {
+        let chain = self.fallback_chain(locale);
+
+        self.localize_chain(chain)
+    }
+
+    /// Choose the best locale for an HTTP `Accept-Language` header by `negotiate_locale`, and get a view of this catalog for it.
+    ///
+    /// ```
+    /// use std::collections::BTreeMap;
+    ///
+    /// use map_to_javascript_html::{Catalog, MapToJavaScriptHTML};
+    ///
+    /// let mut en = BTreeMap::new();
+    /// en.insert("hello", "Hello!");
+    ///
+    /// let mut de = BTreeMap::new();
+    /// de.insert("hello", "Hallo!");
+    ///
+    /// let mut catalog = Catalog::new("en");
+    ///
+    /// catalog.insert_locale("en", &en);
+    /// catalog.insert_locale("de", &de);
+    ///
+    /// let localized = catalog.negotiate("fr-CH, fr;q=0.9, de;q=0.7, *;q=0.5");
+    ///
+    /// assert_eq!("de", localized.locale());
+    /// assert_eq!(
+    ///     "_text['hello']='Hallo!';",
+    ///     localized.to_javascript_html("_text")
+    /// );
+    /// ```
+    pub fn negotiate(&self, accept_language: &str) -> LocalizedCatalog<'_> {
+        let available: Vec<&str> = self.locales().collect();
+
+        let negotiation = negotiate_locale(accept_language, &available, &self.default_locale);
+
+        self.localize_chain(negotiation.fallback_chain)
+    }
+
+    fn localize_chain<'a>(&'a self, chain: Vec<&'a str>) -> LocalizedCatalog<'a> {
+        let chain = chain
+            .into_iter()
+            .map(|locale| {
+                let (locale, entries) = self.find_locale(locale).unwrap();
+
+                (locale.as_str(), entries)
+            })
+            .collect();
+
+        LocalizedCatalog {
+            default_locale: &self.default_locale,
+            chain,
+        }
+    }
+}
+
+/// A view of a `Catalog` for a locale, created by `Catalog::localize`. The keys are sorted.
+#[derive(Debug, Clone)]
+pub struct LocalizedCatalog<'a> {
+    default_locale: &'a str,
+    chain:          Vec<(&'a str, &'a Entries)>,
+}
+
+impl<'a> LocalizedCatalog<'a> {
+    /// The most specific available locale of the fallback chain, or the default locale of the catalog if no locale is available.
+    #[inline]
+    pub fn locale(&self) -> &'a str {
+        self.chain.first().map_or(self.default_locale, |(locale, _)| locale)
+    }
+
+    /// The available locales used to resolve the keys.
+    #[inline]
+    pub fn fallback_chain(&self) -> Vec<&'a str> {
+        self.chain.iter().map(|(locale, _)| *locale).collect()
+    }
+
+    /// Get the value of a key from the first locale which has it.
+    #[inline]
+    pub fn get<KS: ?Sized + Ord>(&self, key: &KS) -> Option<&'a JsValue<'static>>
+    where
+        String: Borrow<KS>, {
+        self.chain.iter().find_map(|(_, entries)| entries.get(key))
+    }
+
+    /// The merged entries.
+    pub fn entries(&self) -> BTreeMap<&'a str, &'a JsValue<'static>> {
+        let mut merged = BTreeMap::new();
+
+        for (_, entries) in self.chain.iter().rev() {
+            for (key, value) in entries.iter() {
+                merged.insert(key.as_str(), value);
+            }
+        }
+
+        merged
+    }
+}
+
+impl<'c> MapToJavaScriptHTML<String> for LocalizedCatalog<'c> {
+    fn to_javascript_html_to_vec<'a, S: Display>(
+        &self,
+        variable_name: S,
+        output: &'a mut Vec<u8>,
+    ) -> &'a [u8] {
+        let variable_name = format!("{}", variable_name);
+
+        let current_length = output.len();
+
+        for (key, value) in self.entries() {
+            write_entry_to_vec(&variable_name, key, value, output);
+        }
+
+        &output[current_length..]
+    }
+
+    #[cfg(feature = "std")]
+    fn to_javascript_html_to_writer<S: Display, W: Write>(
+        &self,
+        variable_name: S,
+        output: &mut W,
+    ) -> Result<(), io::Error> {
+        let variable_name = format!("{}", variable_name);
+
+        let mut buffer = Vec::new();
+
+        for (key, value) in self.entries() {
+            buffer.clear();
+            write_entry_to_vec(&variable_name, key, value, &mut buffer);
+            output.write_all(&buffer)?;
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    fn to_javascript_html_with_keys_to_vec<'a, S: Display, KS: ?Sized + Display + Ord + Hash>(
+        &self,
+        variable_name: S,
+        keys: &[&KS],
+        output: &'a mut Vec<u8>,
+    ) -> &'a [u8]
+    where
+        String: Borrow<KS>, {
+        let variable_name = format!("{}", variable_name);
+
+        let current_length = output.len();
+
+        for key in keys.iter() {
+            let value = self.get(*key).unwrap_or(&JsValue::Undefined);
+
+            write_entry_to_vec(&variable_name, &format!("{}", key), value, output);
+        }
+
+        &output[current_length..]
+    }
+
+    #[cfg(feature = "std")]
+    fn to_javascript_html_with_keys_to_writer<
+        S: Display,
+        W: Write,
+        KS: ?Sized + Display + Ord + Hash,
+    >(
+        &self,
+        variable_name: S,
+        keys: &[&KS],
+        output: &mut W,
+    ) -> Result<(), io::Error>
+    where
+        String: Borrow<KS>, {
+        let variable_name = format!("{}", variable_name);
+
+        let mut buffer = Vec::new();
+
+        for key in keys.iter() {
+            let value = self.get(*key).unwrap_or(&JsValue::Undefined);
+
+            buffer.clear();
+            write_entry_to_vec(&variable_name, &format!("{}", key), value, &mut buffer);
+            output.write_all(&buffer)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'c> JavaScriptEntries for LocalizedCatalog<'c> {
+    #[inline]
+    fn javascript_entries(&self) -> Box<dyn Iterator<Item = (Cow<'_, str>, JsValue<'_>)> + '_> {
+        Box::new(self.entries().into_iter().map(|(key, value)| (Cow::Borrowed(key), value.clone())))
+    }
+
+    #[inline]
+    fn javascript_entry(&self, key: &str) -> Option<JsValue<'_>> {
+        self.get(key).cloned()
+    }
+}
@@ -0,0 +1,180 @@
+use alloc::vec::Vec;
+
+use super::locale_eq;
+
+/// A language range of an `Accept-Language` header with its quality value.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LanguageRange<'a> {
+    /// A BCP 47 language tag, such as `fr-CA`, or `*`.
+    pub tag:     &'a str,
+    /// The quality value between `0` and `1`.
+    pub quality: f32,
+}
+
+/// Check whether a string is a well-formed language tag in the form of `alpha *("-" alphanum)`, where every subtag has 1 to 8 characters.
+fn is_language_tag(tag: &str) -> bool {
+    tag.split('-').enumerate().all(|(i, subtag)| {
+        (1..=8).contains(&subtag.len())
+            && if i == 0 {
+                subtag.bytes().all(|b| b.is_ascii_alphabetic())
+            } else {
+                subtag.bytes().all(|b| b.is_ascii_alphanumeric())
+            }
+    })
+}
+
+/// Parse a quality value, which has at most three decimal places.
+fn parse_quality(value: &str) -> Option<f32> {
+    let (integer, fraction) = value.split_once('.').unwrap_or((value, ""));
+
+    if fraction.len() > 3 || !fraction.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+
+    match integer {
+        "0" => Some(format!("0.{}", fraction).parse().unwrap_or(0.0)),
+        "1" if fraction.bytes().all(|b| b == b'0') => Some(1.0),
+        _ => None,
+    }
+}
+
+/// Parse the value of an HTTP `Accept-Language` header, such as `fr-CA, fr;q=0.9, en;q=0.8, *;q=0.1`.
+///
+/// The ranges are sorted by their quality values in descending order, and the ranges which have the same quality value keep their order. Malformed ranges and ranges with the quality value `0` are ignored.
+pub fn parse_accept_language(header: &str) -> Vec<LanguageRange<'_>> {
+    let mut ranges = Vec::new();
+
+    for item in header.split(',') {
+        let mut parts = item.split(';');
+
+        let tag = parts.next().unwrap_or("").trim();
+
+        if tag != "*" && !is_language_tag(tag) {
+            continue;
+        }
+
+        let mut quality = Some(1.0);
+
+        for parameter in parts {
+            match parameter.split_once('=') {
+                Some((name, value)) if name.trim().eq_ignore_ascii_case("q") => {
+                    quality = parse_quality(value.trim());
+                },
+                _ => (),
+            }
+        }
+
+        match quality {
+            Some(quality) if quality > 0.0 => ranges.push(LanguageRange {
+                tag,
+                quality,
+            }),
+            _ => (),
+        }
+    }
+
+    // a stable sort keeps the order of the ranges which have the same quality value
+    ranges.sort_by(|a, b| b.quality.partial_cmp(&a.quality).unwrap());
+
+    ranges
+}
+
+/// The available locales used to resolve the keys for a locale, from the most specific one to the default locale.
+pub(super) fn fallback_chain<'a>(
+    locale: &str,
+    available: &[&'a str],
+    default_locale: &str,
+) -> Vec<&'a str> {
+    let mut chain: Vec<&'a str> = Vec::new();
+
+    let mut push = |locale: &str| {
+        if let Some(l) = available.iter().find(|l| locale_eq(l, locale)) {
+            if !chain.contains(l) {
+                chain.push(l);
+            }
+        }
+    };
+
+    let mut tag = locale;
+
+    while !tag.is_empty() {
+        push(tag);
+
+        tag = match tag.rfind(['-', '_']) {
+            Some(index) => &tag[..index],
+            None => "",
+        };
+    }
+
+    push(default_locale);
+
+    chain
+}
+
+/// The result of the negotiation of a locale.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Negotiation<'a> {
+    /// The best available locale, or the default locale if no available locale matches.
+    pub locale:         &'a str,
+    /// The available locales used to resolve the keys, starting with `locale` and ending with the default locale if it is available.
+    pub fallback_chain: Vec<&'a str>,
+}
+
+/// Find the best available locale for an `Accept-Language` header.
+///
+/// The ranges are tried in the order of their quality values. A range matches an available locale which is the same as the range or the range with its last subtags removed, such as `fr` for `fr-CA`. If there is no such locale, the first available locale which starts with the range, such as `en-GB` for `en`, is matched. `*` matches the default locale. The comparison is case-insensitive and treats `_` as `-`.
+///
+/// ```
+/// use map_to_javascript_html::negotiate_locale;
+///
+/// let negotiation =
+///     negotiate_locale("fr-CA, en;q=0.5", &["en", "fr", "de"], "en");
+///
+/// assert_eq!("fr", negotiation.locale);
+/// assert_eq!(vec!["fr", "en"], negotiation.fallback_chain);
+/// ```
+pub fn negotiate_locale<'a>(
+    accept_language: &str,
+    available: &[&'a str],
+    default_locale: &'a str,
+) -> Negotiation<'a> {
+    let find = |tag: &str| available.iter().copied().find(|l| locale_eq(l, tag));
+
+    let mut best = None;
+
+    for range in parse_accept_language(accept_language) {
+        if range.tag == "*" {
+            break;
+        }
+
+        let mut tag = range.tag;
+
+        while !tag.is_empty() && best.is_none() {
+            best = find(tag);
+
+            tag = match tag.rfind('-') {
+                Some(index) => &tag[..index],
+                None => "",
+            };
+        }
+
+        if best.is_none() {
+            best = available.iter().copied().find(|l| {
+                l.len() > range.tag.len()
+                    && matches!(l.as_bytes()[range.tag.len()], b'-' | b'_')
+                    && l[..range.tag.len()].eq_ignore_ascii_case(range.tag)
+            });
+        }
+
+        if best.is_some() {
+            break;
+        }
+    }
+
+    let locale = best.or_else(|| find(default_locale)).unwrap_or(default_locale);
+
+    Negotiation {
+        locale,
+        fallback_chain: fallback_chain(locale, available, default_locale),
+    }
+}
@@ -0,0 +1,208 @@
+use std::{
+    collections::BTreeMap,
+    path::PathBuf,
+    sync::{
+        mpsc::{self, RecvTimeoutError},
+        Arc, Mutex, RwLock,
+    },
+    thread,
+    time::Duration,
+};
+
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+
+use super::{Catalog, CatalogError};
+use crate::MapToJavaScriptHTML;
+
+/// How long the directory must be quiet after a change before it is reloaded, so that a file which is truncated and then rewritten is not loaded half-written.
+const DEBOUNCE: Duration = Duration::from_millis(100);
+
+/// A loaded catalog and the rendered output of every locale.
+struct Snapshot {
+    catalog:  Arc<Catalog>,
+    rendered: BTreeMap<String, Arc<[u8]>>,
+}
+
+impl Snapshot {
+    #[inline]
+    fn rendered(&self, locale: &str) -> Arc<[u8]> {
+        let chain = self.catalog.fallback_chain(locale);
+
+        chain
+            .first()
+            .and_then(|locale| self.rendered.get(*locale))
+            .cloned()
+            .unwrap_or_else(|| Arc::from(&[][..]))
+    }
+}
+
+/// Where and how a catalog is loaded.
+struct Source {
+    dir:            PathBuf,
+    default_locale: String,
+    variable_name:  String,
+}
+
+impl Source {
+    fn load(&self) -> Result<Snapshot, CatalogError> {
+        let catalog = Catalog::load_dir(&self.dir, self.default_locale.as_str())?;
+
+        let rendered = catalog
+            .locales()
+            .map(|locale| {
+                let mut output = Vec::new();
+
+                catalog
+                    .localize(locale)
+                    .to_javascript_html_to_vec(&self.variable_name, &mut output);
+
+                (String::from(locale), Arc::from(output))
+            })
+            .collect();
+
+        Ok(Snapshot {
+            catalog: Arc::new(catalog),
+            rendered,
+        })
+    }
+}
+
+struct Shared {
+    source:   Source,
+    snapshot: RwLock<Arc<Snapshot>>,
+    error:    Mutex<Option<CatalogError>>,
+}
+
+impl Shared {
+    fn reload(&self) -> Result<(), CatalogError> {
+        let snapshot = Arc::new(self.source.load()?);
+
+        *self.snapshot.write().unwrap() = snapshot;
+
+        // the error of an earlier reload is stale now
+        *self.error.lock().unwrap() = None;
+
+        Ok(())
+    }
+
+    #[inline]
+    fn snapshot(&self) -> Arc<Snapshot> {
+        self.snapshot.read().unwrap().clone()
+    }
+}
+
+/// A catalog loaded from a directory by `Catalog::load_dir`, which is reloaded when the files in the directory change. The output of every locale is rendered when loading, so reading it is cheap.
+///
+/// The changes are coalesced, and the directory is reloaded once it has been quiet for 100 milliseconds, so a file which an editor truncates and then rewrites is loaded after it is complete. If a reload fails, such as when a file is saved with a syntax error, the previous snapshot is kept and the error can be taken by `take_error` until a later reload succeeds.
+pub struct CatalogWatcher {
+    shared:   Arc<Shared>,
+    _watcher: RecommendedWatcher,
+}
+
+impl CatalogWatcher {
+    /// Load a directory and start watching it. `variable_name` is used to render the output, and it will not be encoded in HTML.
+    pub fn new<P: Into<PathBuf>, L: Into<String>, S: core::fmt::Display>(
+        dir: P,
+        default_locale: L,
+        variable_name: S,
+    ) -> Result<CatalogWatcher, CatalogError> {
+        let source = Source {
+            dir:            dir.into(),
+            default_locale: default_locale.into(),
+            variable_name:  format!("{}", variable_name),
+        };
+
+        let snapshot = source.load()?;
+
+        let shared = Arc::new(Shared {
+            source,
+            snapshot: RwLock::new(Arc::new(snapshot)),
+            error: Mutex::new(None),
+        });
+
+        let (sender, receiver) = mpsc::channel();
+
+        let handler_shared = Arc::downgrade(&shared);
+
+        let mut watcher =
+            notify::recommended_watcher(move |event: notify::Result<notify::Event>| match event {
+                Ok(event) => {
+                    if let EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_) =
+                        event.kind
+                    {
+                        let _ = sender.send(());
+                    }
+                },
+                Err(error) => {
+                    if let Some(shared) = handler_shared.upgrade() {
+                        *shared.error.lock().unwrap() = Some(CatalogError::WatchError(error));
+                    }
+                },
+            })?;
+
+        let reloader_shared = Arc::downgrade(&shared);
+
+        // the channel is disconnected when the watcher is dropped, which stops the thread
+        thread::spawn(move || {
+            while receiver.recv().is_ok() {
+                // coalesce the events until the directory is quiet
+                loop {
+                    match receiver.recv_timeout(DEBOUNCE) {
+                        Ok(()) => continue,
+                        Err(RecvTimeoutError::Timeout) => break,
+                        Err(RecvTimeoutError::Disconnected) => return,
+                    }
+                }
+
+                let shared = match reloader_shared.upgrade() {
+                    Some(shared) => shared,
+                    None => return,
+                };
+
+                if let Err(error) = shared.reload() {
+                    *shared.error.lock().unwrap() = Some(error);
+                }
+            }
+        });
+
+        watcher.watch(&shared.source.dir, RecursiveMode::NonRecursive)?;
+
+        Ok(CatalogWatcher {
+            shared,
+            _watcher: watcher,
+        })
+    }
+
+    /// Get the rendered output of a locale, which is resolved through its fallback chain. The output is empty if no locale of the catalog is available.
+    #[inline]
+    pub fn rendered(&self, locale: &str) -> Arc<[u8]> {
+        self.shared.snapshot().rendered(locale)
+    }
+
+    /// Get the rendered output of the best locale for an HTTP `Accept-Language` header.
+    pub fn negotiate(&self, accept_language: &str) -> Arc<[u8]> {
+        let snapshot = self.shared.snapshot();
+
+        let locale = snapshot.catalog.negotiate(accept_language).locale();
+
+        snapshot.rendered(locale)
+    }
+
+    /// Get the current catalog.
+    #[inline]
+    pub fn catalog(&self) -> Arc<Catalog> {
+        self.shared.snapshot().catalog.clone()
+    }
+
+    /// Reload the directory now.
+    #[inline]
+    pub fn reload(&self) -> Result<(), CatalogError> {
+        self.shared.reload()
+    }
+
+    /// Take the error of the last failed reload which was triggered by a change. It is cleared when a reload succeeds.
+    #[inline]
+    pub fn take_error(&self) -> Option<CatalogError> {
+        self.shared.error.lock().unwrap().take()
+    }
+}
@@ -0,0 +1,33 @@
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+};
+
+/// Errors which can occur when running code in the embedded JavaScript engine.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EngineError {
+    /// The code throws an error or has a syntax error.
+    Evaluation(String),
+    /// A value cannot be converted to a `JsValue`, such as a symbol, a `BigInt` or a string with a lone surrogate.
+    UnsupportedValue,
+    /// The variable is not an object.
+    NotAnObject,
+    /// The value of the key in the variable is not the same as the value in the map, or only one of them has the key.
+    Mismatch { key: String },
+}
+
+impl Display for EngineError {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            EngineError::Evaluation(message) => write!(f, "the evaluation failed: {}", message),
+            EngineError::UnsupportedValue => f.write_str("a value is not supported"),
+            EngineError::NotAnObject => f.write_str("the variable is not an object"),
+            EngineError::Mismatch {
+                key,
+            } => write!(f, "the value of {:?} does not match the map", key),
+        }
+    }
+}
+
+impl Error for EngineError {}
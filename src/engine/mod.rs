@@ -0,0 +1,219 @@
+mod error;
+
+use std::{borrow::Cow, collections::BTreeMap};
+
+use boa_engine::{js_string, Context, Source};
+pub use error::*;
+
+use crate::{JavaScriptEntries, JsFunction, JsNumber, JsValue};
+
+/// A function which converts a value to nested arrays of `[tag, payload]`, so that its properties can be read without the internal methods of the engine.
+const WALK: &str = concat!(
+    "(function walk(v){",
+    "if(typeof v==='function'){return ['f',String(v)];}",
+    "if(Array.isArray(v)){return ['a',v.map(function(e){return walk(e);})];}",
+    "if(v!==null&&typeof v==='object'){",
+    "return ['o',Object.keys(v).map(function(k){return [k,walk(v[k])];})];",
+    "}",
+    "return ['p',v];",
+    "})",
+);
+
+#[inline]
+fn evaluate(code: &str, context: &mut Context) -> Result<boa_engine::JsValue, EngineError> {
+    context
+        .eval(Source::from_bytes(code))
+        .map_err(|error| EngineError::Evaluation(format!("{}", error)))
+}
+
+#[inline]
+fn get(
+    value: &boa_engine::JsValue,
+    index: usize,
+    context: &mut Context,
+) -> Result<boa_engine::JsValue, EngineError> {
+    let object = value.as_object().ok_or(EngineError::UnsupportedValue)?;
+
+    object.get(index, context).map_err(|error| EngineError::Evaluation(format!("{}", error)))
+}
+
+fn get_items(
+    value: &boa_engine::JsValue,
+    context: &mut Context,
+) -> Result<Vec<boa_engine::JsValue>, EngineError> {
+    let object = value.as_object().ok_or(EngineError::UnsupportedValue)?;
+
+    let length = object
+        .get(js_string!("length"), context)
+        .map_err(|error| EngineError::Evaluation(format!("{}", error)))?
+        .as_number()
+        .ok_or(EngineError::UnsupportedValue)? as usize;
+
+    (0..length).map(|i| get(value, i, context)).collect()
+}
+
+fn get_string(value: &boa_engine::JsValue) -> Result<String, EngineError> {
+    value.as_string().and_then(|s| s.to_std_string().ok()).ok_or(EngineError::UnsupportedValue)
+}
+
+/// Convert a value walked by `WALK`.
+fn convert(
+    walked: &boa_engine::JsValue,
+    context: &mut Context,
+) -> Result<JsValue<'static>, EngineError> {
+    let tag = get_string(&get(walked, 0, context)?)?;
+    let payload = get(walked, 1, context)?;
+
+    match tag.as_str() {
+        "f" => Ok(JsValue::Function(JsFunction {
+            code: get_string(&payload)?
+        })),
+        "a" => {
+            let items = get_items(&payload, context)?;
+
+            items
+                .iter()
+                .map(|item| convert(item, context))
+                .collect::<Result<_, _>>()
+                .map(JsValue::Array)
+        },
+        "o" => {
+            let entries = get_items(&payload, context)?;
+
+            let mut object = Vec::with_capacity(entries.len());
+
+            for entry in entries {
+                let key = get_string(&get(&entry, 0, context)?)?;
+                let value = convert(&get(&entry, 1, context)?, context)?;
+
+                object.push((Cow::Owned(key), value));
+            }
+
+            Ok(JsValue::Object(object))
+        },
+        _ => match payload {
+            boa_engine::JsValue::Undefined => Ok(JsValue::Undefined),
+            boa_engine::JsValue::Null => Ok(JsValue::Null),
+            boa_engine::JsValue::Boolean(b) => Ok(JsValue::Bool(b)),
+            boa_engine::JsValue::Integer(n) => Ok(JsValue::Number(JsNumber::from(n))),
+            boa_engine::JsValue::Rational(n) => Ok(JsValue::Number(JsNumber::from(n))),
+            boa_engine::JsValue::String(_) => {
+                Ok(JsValue::String(Cow::Owned(get_string(&payload)?)))
+            },
+            _ => Err(EngineError::UnsupportedValue),
+        },
+    }
+}
+
+/// Run JavaScript code as a script in the embedded JavaScript engine, [Boa](https://boajs.dev/), and read the value of an expression, such as the name of a variable, after it. The code written by the `MapToJavaScriptHTML` trait needs a declaration of the variable before it, such as `var _text={};`.
+///
+/// The own enumerable properties of objects are read in the order of `Object.keys`, and functions are read with the code converted from them by the engine.
+///
+/// ```
+/// use std::collections::BTreeMap;
+///
+/// use map_to_javascript_html::{
+///     evaluate_javascript_html, JsValue, MapToJavaScriptHTML,
+/// };
+///
+/// let mut map = BTreeMap::new();
+///
+/// map.insert("hello", "It's </script>");
+///
+/// let code = format!("var _text={{}};{}", map.to_javascript_html("_text"));
+///
+/// assert_eq!(
+///     JsValue::Object(vec![(
+///         "hello".into(),
+///         JsValue::String("It's </script>".into())
+///     )]),
+///     evaluate_javascript_html(&code, "_text").unwrap()
+/// );
+/// ```
+pub fn evaluate_javascript_html(
+    code: &str,
+    expression: &str,
+) -> Result<JsValue<'static>, EngineError> {
+    let mut context = Context::default();
+
+    evaluate(code, &mut context)?;
+
+    let walked = evaluate(&format!("{}({})", WALK, expression), &mut context)?;
+
+    convert(&walked, &mut context)
+}
+
+/// Check whether two values are the same in JavaScript. Numbers are compared by their values, the order of the keys of objects is ignored, and functions are only checked to be functions.
+fn same_value(expected: &JsValue, actual: &JsValue) -> bool {
+    match (expected, actual) {
+        (JsValue::Number(a), JsValue::Number(b)) => {
+            let (a, b) = (a.as_f64(), b.as_f64());
+
+            a == b || (a.is_nan() && b.is_nan())
+        },
+        (JsValue::String(a), JsValue::String(b)) => a == b,
+        (JsValue::Array(a), JsValue::Array(b)) => {
+            a.len() == b.len() && a.iter().zip(b.iter()).all(|(a, b)| same_value(a, b))
+        },
+        (JsValue::Object(a), JsValue::Object(b)) => same_object(a, b).is_none(),
+        (JsValue::Function(_), JsValue::Function(_)) => true,
+        (a, b) => a == b,
+    }
+}
+
+/// Find a key whose values of two objects are not the same. When a key appears more than once in an object, the last value is used, as JavaScript does.
+fn same_object<'a>(
+    expected: &'a [(Cow<str>, JsValue)],
+    actual: &'a [(Cow<str>, JsValue)],
+) -> Option<&'a str> {
+    let expected: BTreeMap<&str, &JsValue> =
+        expected.iter().map(|(key, value)| (key.as_ref(), value)).collect();
+    let actual: BTreeMap<&str, &JsValue> =
+        actual.iter().map(|(key, value)| (key.as_ref(), value)).collect();
+
+    for (key, value) in expected.iter() {
+        match actual.get(key) {
+            Some(actual_value) if same_value(value, actual_value) => (),
+            _ => return Some(key),
+        }
+    }
+
+    actual.keys().find(|key| !expected.contains_key(*key)).copied()
+}
+
+/// Run JavaScript code by `evaluate_javascript_html`, and check that the value of an expression is an object which has the same entries as a map.
+///
+/// A string value of the map is compared with the string read by the engine, so it cannot contain backslashes which start escape sequences.
+///
+/// ```
+/// use std::collections::BTreeMap;
+///
+/// use map_to_javascript_html::{verify_javascript_html, MapToJavaScriptHTML};
+///
+/// let mut map = BTreeMap::new();
+///
+/// map.insert("hello", "It's </script>");
+///
+/// let code = format!("var _text={{}};{}", map.to_javascript_html("_text"));
+///
+/// assert!(verify_javascript_html(&map, &code, "_text").is_ok());
+/// ```
+pub fn verify_javascript_html<M: ?Sized + JavaScriptEntries>(
+    map: &M,
+    code: &str,
+    expression: &str,
+) -> Result<(), EngineError> {
+    let actual = match evaluate_javascript_html(code, expression)? {
+        JsValue::Object(object) => object,
+        _ => return Err(EngineError::NotAnObject),
+    };
+
+    let expected: Vec<(Cow<str>, JsValue)> = map.javascript_entries().collect();
+
+    match same_object(&expected, &actual) {
+        Some(key) => Err(EngineError::Mismatch {
+            key: String::from(key)
+        }),
+        None => Ok(()),
+    }
+}
@@ -0,0 +1,311 @@
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::io::{self, Write};
+
+/// Check whether the bytes after a `<` would make it start `</script`, `<script` or `<!--`, which can change how HTML parsers handle a `<script>` element.
+#[inline]
+fn is_html_sensitive(rest: &[u8]) -> bool {
+    let starts_with_ignore_case = |prefix: &[u8]| {
+        rest.len() >= prefix.len() && rest[..prefix.len()].eq_ignore_ascii_case(prefix)
+    };
+
+    starts_with_ignore_case(b"/script")
+        || starts_with_ignore_case(b"script")
+        || rest.starts_with(b"!--")
+}
+
+/// Check whether the bytes after a backslash which starts an escape sequence would make `\x` or `\u` without enough hexadecimal digits, which is a syntax error in JavaScript.
+#[inline]
+fn is_invalid_escape(rest: &[u8]) -> bool {
+    let is_hex =
+        |bytes: Option<&[u8]>| bytes.map_or(false, |bytes| bytes.iter().all(u8::is_ascii_hexdigit));
+
+    match rest.first() {
+        Some(b'x') => !is_hex(rest.get(1..3)),
+        Some(b'u') if rest.get(1) == Some(&b'{') => {
+            let digits = &rest[2..];
+            let digits = &digits[..digits.iter().take_while(|b| b.is_ascii_hexdigit()).count()];
+
+            let end = 2 + digits.len();
+
+            // the code point is at most `10FFFF`, and leading zeros are allowed
+            let digits = &digits[digits.iter().take_while(|b| **b == b'0').count()..];
+
+            end == 2
+                || rest.get(end) != Some(&b'}')
+                || digits.len() > 6
+                || core::str::from_utf8(digits)
+                    .ok()
+                    .and_then(|digits| u32::from_str_radix(digits, 16).ok())
+                    .map_or(false, |code_point| code_point > 0x10FFFF)
+        },
+        Some(b'u') => !is_hex(rest.get(1..5)),
+        _ => false,
+    }
+}
+
+/// Find the control character at the start of the bytes of a `str`, which are C0 and C1 control characters, `DEL`, and the line separators `U+2028` and `U+2029`. Return the code point and its length in bytes.
+#[inline]
+fn control_character(bytes: &[u8]) -> Option<(u32, usize)> {
+    match bytes[0] {
+        0x00..=0x1F | 0x7F => Some((bytes[0] as u32, 1)),
+        // C1 control characters, U+0080 to U+009F
+        0xC2 => match bytes.get(1) {
+            Some(b @ 0x80..=0x9F) => Some((*b as u32, 2)),
+            _ => None,
+        },
+        // U+2028 and U+2029
+        0xE2 => match bytes.get(1..3) {
+            Some([0x80, b @ (0xA8 | 0xA9)]) => Some((0x2028 + (*b - 0xA8) as u32, 3)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Write the escape sequence of a control character without its backslash, such as `n` or `u0000`.
+#[inline]
+fn push_control_character_escape(code_point: u32, output: &mut Vec<u8>) {
+    match code_point {
+        0x08 => output.push(b'b'),
+        0x09 => output.push(b't'),
+        0x0A => output.push(b'n'),
+        0x0C => output.push(b'f'),
+        0x0D => output.push(b'r'),
+        _ => output.extend_from_slice(format!("u{:04x}", code_point).as_bytes()),
+    }
+}
+
+/// Encode text used in a single-quoted string in the `<script>` element. A backslash in the text starts an escape sequence, so `\'` and `\n` are kept.
+///
+/// * `'` => `\'`, unless it is escaped
+/// * `</script`, `<script`, `<!--` => `<\/script`, `<\script`, `<\!--`
+/// * control characters, `U+2028` and `U+2029` => `\b`, `\t`, `\n`, `\f`, `\r` or `\uXXXX`, unless a line terminator is escaped, which is a line continuation
+/// * a backslash which would start an invalid `\x` or `\u` escape sequence, or escape the closing quote => `\\`
+#[inline]
+pub(crate) fn encode_script_single_quoted_text_to_vec<S: AsRef<str>>(
+    text: S,
+    output: &mut Vec<u8>,
+) -> &[u8] {
+    encode_script_quoted_text_to_vec(text, b'\'', output)
+}
+
+/// Encode text used in a string quoted by `quote`, which is `'` or `"`, in the `<script>` element, in the same way as `encode_script_single_quoted_text_to_vec`.
+pub(crate) fn encode_script_quoted_text_to_vec<S: AsRef<str>>(
+    text: S,
+    quote: u8,
+    output: &mut Vec<u8>,
+) -> &[u8] {
+    let text_bytes = text.as_ref().as_bytes();
+
+    let current_length = output.len();
+
+    output.reserve(text_bytes.len());
+
+    let mut start = 0;
+    let mut p = 0;
+
+    // whether the previous byte is a backslash which starts an escape sequence
+    let mut escaped = false;
+
+    while p < text_bytes.len() {
+        if let Some((code_point, length)) = control_character(&text_bytes[p..]) {
+            if escaped && matches!(code_point, 0x0A | 0x0D | 0x2028 | 0x2029) {
+                // an escaped line terminator is a line continuation in JavaScript, which adds nothing to the string and cannot end it, so it is kept as it is
+                p += length;
+
+                if code_point == 0x0D && text_bytes.get(p) == Some(&b'\n') {
+                    p += 1;
+                }
+
+                escaped = false;
+
+                continue;
+            }
+
+            output.extend_from_slice(&text_bytes[start..p]);
+
+            // other escaped control characters are themselves, so the backslash is reused
+            if !escaped {
+                output.push(b'\\');
+            }
+
+            push_control_character_escape(code_point, output);
+
+            escaped = false;
+            p += length;
+            start = p;
+
+            continue;
+        }
+
+        let e = text_bytes[p];
+
+        let replacement: &[u8] = match e {
+            b'\'' if !escaped && quote == b'\'' => b"\\'",
+            b'"' if !escaped && quote == b'"' => b"\\\"",
+            b'<' if is_html_sensitive(&text_bytes[(p + 1)..]) => b"<\\",
+            b'\\' if !escaped && is_invalid_escape(&text_bytes[(p + 1)..]) => b"\\\\",
+            _ => {
+                escaped = e == b'\\' && !escaped;
+                p += 1;
+
+                continue;
+            },
+        };
+
+        output.extend_from_slice(&text_bytes[start..p]);
+        output.extend_from_slice(replacement);
+
+        escaped = false;
+        p += 1;
+        start = p;
+    }
+
+    output.extend_from_slice(&text_bytes[start..]);
+
+    if escaped {
+        output.push(b'\\');
+    }
+
+    &output[current_length..]
+}
+
+/// Choose the quote which needs fewer escapes for text used in a string, which is `"` if the text has fewer unescaped `"` than unescaped `'`, or `'` otherwise.
+pub(crate) fn shortest_quote<S: AsRef<str>>(text: S) -> u8 {
+    let mut single = 0;
+    let mut double = 0;
+
+    // whether the previous byte is a backslash which starts an escape sequence
+    let mut escaped = false;
+
+    for e in text.as_ref().bytes() {
+        match e {
+            b'\'' if !escaped => single += 1,
+            b'"' if !escaped => double += 1,
+            _ => (),
+        }
+
+        escaped = e == b'\\' && !escaped;
+    }
+
+    if double < single {
+        b'"'
+    } else {
+        b'\''
+    }
+}
+
+/// Encode text used in a single-quoted string in the `<script>` element to a writer.
+#[cfg(feature = "std")]
+#[inline]
+pub(crate) fn encode_script_single_quoted_text_to_writer<S: AsRef<str>, W: Write>(
+    text: S,
+    output: &mut W,
+) -> Result<(), io::Error> {
+    let mut buffer = Vec::new();
+
+    encode_script_single_quoted_text_to_vec(text, &mut buffer);
+
+    output.write_all(&buffer)
+}
+
+/// Encode JavaScript code in the `<script>` element, such as JSON text, whose `<` and control characters other than whitespace can only be in string literals. Tabs, line feeds and carriage returns can be between tokens, so they are kept, and they must have been escaped in string literals.
+///
+/// * `</script`, `<script`, `<!--` => `<\/script`, `<\script`, `<\!--`
+/// * other control characters, `U+2028` and `U+2029` => `\b`, `\f` or `\uXXXX`
+pub(crate) fn encode_script_to_vec<S: AsRef<str>>(text: S, output: &mut Vec<u8>) -> &[u8] {
+    let text_bytes = text.as_ref().as_bytes();
+
+    let current_length = output.len();
+
+    output.reserve(text_bytes.len());
+
+    let mut start = 0;
+    let mut p = 0;
+
+    while p < text_bytes.len() {
+        let control = control_character(&text_bytes[p..])
+            .filter(|(code_point, _)| !matches!(code_point, 0x09 | 0x0A | 0x0D));
+
+        if let Some((code_point, length)) = control {
+            output.extend_from_slice(&text_bytes[start..p]);
+            output.push(b'\\');
+
+            push_control_character_escape(code_point, output);
+
+            p += length;
+            start = p;
+        } else {
+            if text_bytes[p] == b'<' && is_html_sensitive(&text_bytes[(p + 1)..]) {
+                output.extend_from_slice(&text_bytes[start..=p]);
+                output.push(b'\\');
+
+                start = p + 1;
+            }
+
+            p += 1;
+        }
+    }
+
+    output.extend_from_slice(&text_bytes[start..]);
+
+    &output[current_length..]
+}
+
+/// Encode JavaScript code in the `<script>` element to a writer.
+#[cfg(all(feature = "std", any(feature = "serde_json", feature = "toml", feature = "serde_yaml")))]
+#[inline]
+pub(crate) fn encode_script_to_writer<S: AsRef<str>, W: Write>(
+    text: S,
+    output: &mut W,
+) -> Result<(), io::Error> {
+    let mut buffer = Vec::new();
+
+    encode_script_to_vec(text, &mut buffer);
+
+    output.write_all(&buffer)
+}
+
+/// Encode the non-ASCII characters of JavaScript code written after `start` as `\uXXXX` escapes, which are valid in string literals and identifiers, so the code is pure 7-bit ASCII. A character outside the Basic Multilingual Plane is encoded as a surrogate pair. The backslash of an escape sequence which escapes the character is reused.
+pub(crate) fn encode_ascii_only_to_vec(output: &mut Vec<u8>, start: usize) {
+    if output[start..].is_ascii() {
+        return;
+    }
+
+    let code = output.split_off(start);
+
+    // the code is written from `str`s
+    let code = unsafe { core::str::from_utf8_unchecked(&code) };
+
+    let mut backslashes = 0;
+
+    for c in code.chars() {
+        if c.is_ascii() {
+            if c == '\\' {
+                backslashes += 1;
+            } else {
+                backslashes = 0;
+            }
+
+            output.push(c as u8);
+        } else if backslashes % 2 == 1 && matches!(c, '\u{2028}' | '\u{2029}') {
+            // a line continuation adds nothing to the string, so it is removed with its backslash
+            output.pop();
+
+            backslashes = 0;
+        } else {
+            let mut units = [0; 2];
+
+            for (i, unit) in c.encode_utf16(&mut units).iter().enumerate() {
+                if i > 0 || backslashes % 2 == 0 {
+                    output.push(b'\\');
+                }
+
+                output.extend_from_slice(format!("u{:04x}", unit).as_bytes());
+            }
+
+            backslashes = 0;
+        }
+    }
+}
@@ -0,0 +1,347 @@
+use alloc::{boxed::Box, string::String, vec::Vec};
+
+use crate::{escape, JsFunction, PluralRules};
+
+/// An expression which can be compiled into the body of a JavaScript function `function(p){...}`, where `p` is the object of parameters.
+#[cfg_attr(not(feature = "fluent-syntax"), allow(dead_code))]
+#[derive(Debug, Clone)]
+pub(crate) enum Expression {
+    /// A plain text.
+    Text(String),
+    /// A text written like the values of maps, so a backslash in it starts an escape sequence.
+    Literal(String),
+    /// A number literal. It is written as a number when it is used as a selector, or as a text otherwise.
+    Number(String),
+    /// A property of the object of parameters.
+    Parameter(String),
+    /// The concatenation of expressions as a string.
+    Concat(Vec<Expression>),
+    /// Select a variant by the value of the selector. The value is matched against the keys exactly first, and then against the plural categories of the locale if the value is a number.
+    Select {
+        selector: Box<Expression>,
+        variants: Vec<(VariantKey, Expression)>,
+        default:  Box<Expression>,
+    },
+}
+
+#[cfg_attr(not(feature = "fluent-syntax"), allow(dead_code))]
+#[derive(Debug, Clone)]
+pub(crate) enum VariantKey {
+    Number(String),
+    Name(String),
+}
+
+/// Write a decimal number literal, such as `-007.5`, without leading zeros, which are not allowed in the strict mode of JavaScript.
+fn write_number(n: &str, output: &mut Vec<u8>) {
+    let (sign, digits) = match n.strip_prefix('-') {
+        Some(digits) => ("-", digits),
+        None => ("", n),
+    };
+
+    let digits = digits.trim_start_matches('0');
+
+    output.extend_from_slice(sign.as_bytes());
+
+    if digits.is_empty() || digits.starts_with('.') {
+        output.push(b'0');
+    }
+
+    output.extend_from_slice(digits.as_bytes());
+}
+
+const PLURAL_CATEGORIES: [&str; 6] = ["zero", "one", "two", "few", "many", "other"];
+
+/// Check whether a string is an ASCII JavaScript identifier.
+pub(crate) fn is_identifier(s: &str) -> bool {
+    let mut chars = s.chars();
+
+    match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() || c == '_' || c == '$' => (),
+        _ => return false,
+    }
+
+    chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+}
+
+/// Check whether a string is an array index without leading zeros.
+#[inline]
+pub(crate) fn is_index(s: &str) -> bool {
+    !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit()) && (s == "0" || !s.starts_with('0'))
+}
+
+/// Escape a plain text so that it can be written like the values of maps.
+#[cfg_attr(not(feature = "fluent-syntax"), allow(dead_code))]
+pub(crate) fn escape_text(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\u{2028}' => escaped.push_str("\\u2028"),
+            '\u{2029}' => escaped.push_str("\\u2029"),
+            _ => escaped.push(c),
+        }
+    }
+
+    escaped
+}
+
+/// Write a plain text as a single-quoted JavaScript string. Unlike the values of maps, backslashes in the text are not treated as escape sequences.
+fn write_text_literal(text: &str, output: &mut Vec<u8>) {
+    let mut escaped = String::with_capacity(text.len() + 2);
+
+    escaped.push('\'');
+
+    for c in text.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '\'' => escaped.push_str("\\'"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            '\u{2028}' => escaped.push_str("\\u2028"),
+            '\u{2029}' => escaped.push_str("\\u2029"),
+            _ => escaped.push(c),
+        }
+    }
+
+    escaped.push('\'');
+
+    escape::encode_script_to_vec(escaped, output);
+}
+
+impl Expression {
+    fn uses_parameters(&self) -> bool {
+        match self {
+            Expression::Text(_) | Expression::Literal(_) | Expression::Number(_) => false,
+            Expression::Parameter(_) => true,
+            Expression::Concat(expressions) => expressions.iter().any(Expression::uses_parameters),
+            Expression::Select {
+                selector,
+                variants,
+                default,
+            } => {
+                selector.uses_parameters()
+                    || default.uses_parameters()
+                    || variants.iter().any(|(_, e)| e.uses_parameters())
+            },
+        }
+    }
+
+    /// Flatten nested concatenations and merge adjacent texts.
+    fn simplify(self) -> Expression {
+        match self {
+            Expression::Concat(expressions) => {
+                let mut parts: Vec<Expression> = Vec::with_capacity(expressions.len());
+
+                for expression in expressions {
+                    let expression = expression.simplify();
+
+                    let expressions = match expression {
+                        Expression::Concat(expressions) => expressions,
+                        expression => vec![expression],
+                    };
+
+                    for expression in expressions {
+                        match (parts.last_mut(), expression) {
+                            (Some(Expression::Text(last)), Expression::Text(text)) => {
+                                last.push_str(&text)
+                            },
+                            (_, expression) => parts.push(expression),
+                        }
+                    }
+                }
+
+                match parts.len() {
+                    0 => Expression::Text(String::new()),
+                    1 => parts.pop().unwrap(),
+                    _ => Expression::Concat(parts),
+                }
+            },
+            Expression::Select {
+                selector,
+                variants,
+                default,
+            } => {
+                let selector = selector.simplify();
+
+                // select the variant when compiling if the selector is a constant
+                let selected = match &selector {
+                    Expression::Text(text) => Some(
+                        variants
+                            .iter()
+                            .find(|(key, _)| matches!(key, VariantKey::Name(name) if name == text))
+                            .map_or(&*default, |(_, e)| e),
+                    ),
+                    Expression::Number(n) => {
+                        let n = n.parse::<f64>().ok();
+
+                        let exact = variants.iter().find(|(key, _)| {
+                            matches!(key, VariantKey::Number(k) if k.parse::<f64>().ok() == n)
+                        });
+
+                        match exact {
+                            Some((_, e)) => Some(e),
+                            None if variants.iter().all(|(key, _)| {
+                                !matches!(key, VariantKey::Name(name) if PLURAL_CATEGORIES.contains(&name.as_str()))
+                            }) =>
+                            {
+                                Some(&*default)
+                            },
+                            None => None,
+                        }
+                    },
+                    _ => None,
+                };
+
+                match selected {
+                    Some(expression) => expression.clone().simplify(),
+                    None => Expression::Select {
+                        selector: Box::new(selector),
+                        variants: variants.into_iter().map(|(k, e)| (k, e.simplify())).collect(),
+                        default:  Box::new(default.simplify()),
+                    },
+                }
+            },
+            expression => expression,
+        }
+    }
+
+    fn write(&self, plural_locale: &str, output: &mut Vec<u8>) {
+        match self {
+            Expression::Text(text) | Expression::Number(text) => write_text_literal(text, output),
+            Expression::Literal(text) => {
+                output.push(b'\'');
+                escape::encode_script_single_quoted_text_to_vec(text, output);
+                output.push(b'\'');
+            },
+            Expression::Parameter(name) => {
+                output.push(b'p');
+
+                if is_identifier(name) {
+                    output.push(b'.');
+                    output.extend_from_slice(name.as_bytes());
+                } else if is_index(name) {
+                    output.push(b'[');
+                    output.extend_from_slice(name.as_bytes());
+                    output.push(b']');
+                } else {
+                    output.push(b'[');
+                    write_text_literal(name, output);
+                    output.push(b']');
+                }
+            },
+            Expression::Concat(expressions) => {
+                // make sure that `+` concatenates strings instead of adding numbers
+                if !matches!(
+                    expressions.first(),
+                    Some(Expression::Text(_) | Expression::Literal(_))
+                ) {
+                    output.extend_from_slice(b"''+");
+                }
+
+                for (i, expression) in expressions.iter().enumerate() {
+                    if i > 0 {
+                        output.push(b'+');
+                    }
+
+                    expression.write(plural_locale, output);
+                }
+            },
+            Expression::Select {
+                selector,
+                variants,
+                default,
+            } => {
+                let use_plural = variants.iter().any(|(key, _)| {
+                    matches!(key, VariantKey::Name(name) if PLURAL_CATEGORIES.contains(&name.as_str()))
+                });
+
+                output.extend_from_slice(b"(function(v){");
+
+                if use_plural {
+                    // the same CLDR rules as `PluralMessages`, so it does not rely on `Intl.PluralRules`
+                    output.extend_from_slice(b"var c=typeof v==='number'?(");
+                    output.extend_from_slice(
+                        PluralRules::for_locale(plural_locale).to_javascript().as_bytes(),
+                    );
+                    output.extend_from_slice(b")(v):'';");
+                }
+
+                output.extend_from_slice(b"return ");
+
+                for (key, expression) in variants.iter() {
+                    match key {
+                        VariantKey::Number(n) => {
+                            output.extend_from_slice(b"v===");
+                            write_number(n, output);
+                            output.extend_from_slice(b"||v===");
+                            write_text_literal(n, output);
+                        },
+                        VariantKey::Name(name) => {
+                            output.extend_from_slice(b"v===");
+                            write_text_literal(name, output);
+                        },
+                    }
+
+                    output.push(b'?');
+                    expression.write(plural_locale, output);
+                    output.push(b':');
+                }
+
+                for (key, expression) in variants.iter() {
+                    if let VariantKey::Name(name) = key {
+                        if PLURAL_CATEGORIES.contains(&name.as_str()) {
+                            output.extend_from_slice(b"c===");
+                            write_text_literal(name, output);
+                            output.push(b'?');
+                            expression.write(plural_locale, output);
+                            output.push(b':');
+                        }
+                    }
+                }
+
+                default.write(plural_locale, output);
+
+                output.extend_from_slice(b";})(");
+
+                match selector.as_ref() {
+                    Expression::Number(n) => write_number(n, output),
+                    selector => selector.write(plural_locale, output),
+                }
+
+                output.push(b')');
+            },
+        }
+    }
+}
+
+/// Compile an expression into a JavaScript function which returns a string. `plural_locale` is the locale whose embedded CLDR plural rules select the plural variants. If `guard_parameters` is `true`, the function can be called without the object of parameters.
+pub(crate) fn compile_function(
+    expression: Expression,
+    plural_locale: &str,
+    guard_parameters: bool,
+) -> JsFunction {
+    let expression = expression.simplify();
+
+    let mut output = Vec::new();
+
+    if !expression.uses_parameters() {
+        output.extend_from_slice(b"function(){return ");
+    } else if guard_parameters {
+        output.extend_from_slice(b"function(p){p=p||{};return ");
+    } else {
+        output.extend_from_slice(b"function(p){return ");
+    }
+
+    expression.write(plural_locale, &mut output);
+
+    output.extend_from_slice(b";}");
+
+    // the code only contains valid UTF-8 strings and ASCII characters
+    JsFunction {
+        code: unsafe { String::from_utf8_unchecked(output) }
+    }
+}
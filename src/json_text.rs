@@ -0,0 +1,41 @@
+use alloc::string::String;
+use core::fmt::Write;
+
+/// Write a string as a double-quoted JSON string.
+pub(crate) fn push_json_string(s: &str, output: &mut String) {
+    output.push('"');
+
+    for c in s.chars() {
+        match c {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\u{8}' => output.push_str("\\b"),
+            '\u{C}' => output.push_str("\\f"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            '\u{0}'..='\u{1F}' => {
+                write!(output, "\\u{:04x}", c as u32).unwrap();
+            },
+            _ => output.push(c),
+        }
+    }
+
+    output.push('"');
+}
+
+#[cfg(any(feature = "toml", feature = "serde_yaml"))]
+/// Write a float as a JavaScript number. `NaN` and infinities are written as the JavaScript global properties.
+pub(crate) fn push_f64(f: f64, output: &mut String) {
+    if f.is_nan() {
+        output.push_str("NaN");
+    } else if f.is_infinite() {
+        if f.is_sign_positive() {
+            output.push_str("Infinity");
+        } else {
+            output.push_str("-Infinity");
+        }
+    } else {
+        write!(output, "{}", f).unwrap();
+    }
+}
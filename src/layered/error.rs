@@ -0,0 +1,29 @@
+use alloc::string::String;
+use core::fmt::{self, Display, Formatter};
+#[cfg(feature = "std")]
+use std::error::Error;
+
+/// Errors which can occur when merging the layers of a `Layered`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MergeError {
+    /// The key, including the prefix of its layer, is in more than one layer while the conflict policy is `ConflictPolicy::Error`. The layers are counted from zero.
+    DuplicateKey { key: String, first_layer: usize, layer: usize },
+}
+
+impl Display for MergeError {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            MergeError::DuplicateKey {
+                key,
+                first_layer,
+                layer,
+            } => {
+                write!(f, "the key {:?} is in both layer {} and layer {}", key, first_layer, layer)
+            },
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for MergeError {}
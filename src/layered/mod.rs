@@ -0,0 +1,279 @@
+mod error;
+
+use alloc::{
+    borrow::Cow,
+    boxed::Box,
+    collections::{btree_map::Entry, BTreeMap},
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{borrow::Borrow, fmt::Display, hash::Hash};
+#[cfg(feature = "std")]
+use std::io::{self, Write};
+
+pub use error::*;
+
+use crate::{renderer::write_entry_to_vec, JavaScriptEntries, JsValue, MapToJavaScriptHTML};
+
+/// What to do when a key is in more than one layer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictPolicy {
+    /// Keep the value of the first layer which has the key.
+    FirstWins,
+    /// Keep the value of the last layer which has the key, so later layers override earlier ones. This is the default policy.
+    LastWins,
+    /// Fail with `MergeError::DuplicateKey`.
+    Error,
+}
+
+impl Default for ConflictPolicy {
+    #[inline]
+    fn default() -> Self {
+        ConflictPolicy::LastWins
+    }
+}
+
+/// Several maps which are merged into one map, so every key is assigned only once in the output. The maps are called layers, such as a global map, a feature map and a map of overrides for a page.
+///
+/// ```
+/// use std::collections::BTreeMap;
+///
+/// use map_to_javascript_html::{
+///     ConflictPolicy, Layered, MapToJavaScriptHTML,
+/// };
+///
+/// let mut global = BTreeMap::new();
+/// global.insert("title", "My Website");
+/// global.insert("login", "Log in");
+///
+/// let mut page = BTreeMap::new();
+/// page.insert("title", "Settings");
+///
+/// let mut layered = Layered::new(ConflictPolicy::LastWins);
+///
+/// layered.push(&global);
+/// layered.push(&page);
+///
+/// assert_eq!(
+///     "_text['login']='Log in';_text['title']='Settings';",
+///     layered.merge().unwrap().to_javascript_html("_text")
+/// );
+/// ```
+pub struct Layered<'a> {
+    policy: ConflictPolicy,
+    layers: Vec<(Cow<'a, str>, &'a dyn JavaScriptEntries)>,
+}
+
+impl<'a> Layered<'a> {
+    /// Create an empty set of layers with a conflict policy.
+    #[inline]
+    pub fn new(policy: ConflictPolicy) -> Layered<'a> {
+        Layered {
+            policy,
+            layers: Vec::new(),
+        }
+    }
+
+    /// The conflict policy.
+    #[inline]
+    pub fn policy(&self) -> ConflictPolicy {
+        self.policy
+    }
+
+    /// The number of layers.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.layers.len()
+    }
+
+    /// Whether there are no layers.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.layers.is_empty()
+    }
+
+    /// Add a map as the last layer.
+    #[inline]
+    pub fn push<M: JavaScriptEntries>(&mut self, map: &'a M) {
+        self.layers.push((Cow::Borrowed(""), map));
+    }
+
+    /// Add a map as the last layer, and prepend a prefix to each of its keys, such as `checkout.` for a key `title` to be `checkout.title`.
+    #[inline]
+    pub fn push_with_prefix<P: Into<Cow<'a, str>>, M: JavaScriptEntries>(
+        &mut self,
+        prefix: P,
+        map: &'a M,
+    ) {
+        self.layers.push((prefix.into(), map));
+    }
+
+    /// Merge the layers by the conflict policy. Nothing is rendered if there is a conflict which is an error, so the output will never be partial.
+    pub fn merge(&self) -> Result<MergedMap<'a>, MergeError> {
+        let mut entries: BTreeMap<Cow<'a, str>, (usize, JsValue<'a>)> = BTreeMap::new();
+
+        for (layer, (prefix, map)) in self.layers.iter().enumerate() {
+            // copy the reference out so that the entries borrow the map instead of `self`
+            let map: &'a dyn JavaScriptEntries = *map;
+
+            for (key, value) in map.javascript_entries() {
+                let key =
+                    if prefix.is_empty() { key } else { Cow::Owned(format!("{}{}", prefix, key)) };
+
+                match entries.entry(key) {
+                    Entry::Vacant(entry) => {
+                        entry.insert((layer, value));
+                    },
+                    Entry::Occupied(mut entry) => match self.policy {
+                        ConflictPolicy::FirstWins => (),
+                        ConflictPolicy::LastWins => {
+                            entry.insert((layer, value));
+                        },
+                        ConflictPolicy::Error => {
+                            return Err(MergeError::DuplicateKey {
+                                key: entry.key().to_string(),
+                                first_layer: entry.get().0,
+                                layer,
+                            });
+                        },
+                    },
+                }
+            }
+        }
+
+        Ok(MergedMap {
+            entries: entries.into_iter().map(|(key, (_, value))| (key, value)).collect(),
+        })
+    }
+}
+
+/// The result of `Layered::merge`. The keys are sorted.
+#[derive(Debug, Clone)]
+pub struct MergedMap<'a> {
+    entries: BTreeMap<Cow<'a, str>, JsValue<'a>>,
+}
+
+impl<'a> MergedMap<'a> {
+    /// The number of entries.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether there are no entries.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Get the value of a key, including the prefix of its layer.
+    #[inline]
+    pub fn get(&self, key: &str) -> Option<&JsValue<'a>> {
+        self.entries.get(key)
+    }
+}
+
+impl<'m> MapToJavaScriptHTML<String> for MergedMap<'m> {
+    fn to_javascript_html_to_vec<'a, S: Display>(
+        &self,
+        variable_name: S,
+        output: &'a mut Vec<u8>,
+    ) -> &'a [u8] {
+        let variable_name = format!("{}", variable_name);
+
+        let current_length = output.len();
+
+        for (key, value) in self.entries.iter() {
+            write_entry_to_vec(&variable_name, key, value, output);
+        }
+
+        &output[current_length..]
+    }
+
+    #[cfg(feature = "std")]
+    fn to_javascript_html_to_writer<S: Display, W: Write>(
+        &self,
+        variable_name: S,
+        output: &mut W,
+    ) -> Result<(), io::Error> {
+        let variable_name = format!("{}", variable_name);
+
+        let mut buffer = Vec::new();
+
+        for (key, value) in self.entries.iter() {
+            buffer.clear();
+            write_entry_to_vec(&variable_name, key, value, &mut buffer);
+            output.write_all(&buffer)?;
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    fn to_javascript_html_with_keys_to_vec<'a, S: Display, KS: ?Sized + Display + Ord + Hash>(
+        &self,
+        variable_name: S,
+        keys: &[&KS],
+        output: &'a mut Vec<u8>,
+    ) -> &'a [u8]
+    where
+        String: Borrow<KS>, {
+        let variable_name = format!("{}", variable_name);
+
+        let current_length = output.len();
+
+        for key in keys.iter() {
+            let key = format!("{}", key);
+
+            let value = self.get(&key).unwrap_or(&JsValue::Undefined);
+
+            write_entry_to_vec(&variable_name, &key, value, output);
+        }
+
+        &output[current_length..]
+    }
+
+    #[cfg(feature = "std")]
+    fn to_javascript_html_with_keys_to_writer<
+        S: Display,
+        W: Write,
+        KS: ?Sized + Display + Ord + Hash,
+    >(
+        &self,
+        variable_name: S,
+        keys: &[&KS],
+        output: &mut W,
+    ) -> Result<(), io::Error>
+    where
+        String: Borrow<KS>, {
+        let variable_name = format!("{}", variable_name);
+
+        let mut buffer = Vec::new();
+
+        for key in keys.iter() {
+            let key = format!("{}", key);
+
+            let value = self.get(&key).unwrap_or(&JsValue::Undefined);
+
+            buffer.clear();
+            write_entry_to_vec(&variable_name, &key, value, &mut buffer);
+            output.write_all(&buffer)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl<'m> JavaScriptEntries for MergedMap<'m> {
+    #[inline]
+    fn javascript_entries(&self) -> Box<dyn Iterator<Item = (Cow<'_, str>, JsValue<'_>)> + '_> {
+        Box::new(
+            self.entries.iter().map(|(key, value)| (Cow::Borrowed(key.as_ref()), value.clone())),
+        )
+    }
+
+    #[inline]
+    fn javascript_entry(&self, key: &str) -> Option<JsValue<'_>> {
+        self.get(key).cloned()
+    }
+}
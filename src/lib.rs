@@ -63,6 +63,382 @@ let text = map.to_javascript_html_with_keys("_text", &["hello", "welcome"]);
 assert_eq!("_text['hello']='Hello world!';_text['welcome']='Welcome to my website.';", text);
 ```
 
+## Catalog Loaders
+
+The `parse_properties` and `parse_po` functions parse Java `.properties` texts and gettext `.po` texts into `BTreeMap<String, String>`s, which can be used with the `MapToJavaScriptHTML` trait directly. Parse errors report the line and the column where they occur.
+
+```rust
+use map_to_javascript_html::{parse_properties, MapToJavaScriptHTML};
+
+let map = parse_properties("hello=Hello world!\nwelcome=Welcome to my website.").unwrap();
+
+let text = map.to_javascript_html_with_keys("_text", &["hello"]);
+
+assert_eq!("_text['hello']='Hello world!';", text);
+```
+
+The `parse_javascript_html` function reads the code written by the `MapToJavaScriptHTML` trait back into its entries, which is useful for round-trip tests and for importing inline-script catalogs. Escape sequences are unescaped as JavaScript does.
+
+```rust
+use std::collections::BTreeMap;
+
+use map_to_javascript_html::{parse_javascript_html, JsValue, MapToJavaScriptHTML};
+
+let mut map = BTreeMap::new();
+
+map.insert("hello", "It's </script>");
+
+let entries = parse_javascript_html(&map.to_javascript_html("_text"), "_text").unwrap();
+
+assert_eq!(vec![(String::from("hello"), JsValue::String("It's </script>".into()))], entries);
+```
+
+## Catalogs
+
+A `Catalog` holds one map per locale and resolves every key through a fallback chain, such as `fr-CA` → `fr` → `en`. `Catalog::load_dir` loads the `.properties`, `.po`, `.json`, `.toml`, `.yaml` and `.ftl` files in a directory, depending on the enabled features, where the file names are the locales.
+
+```rust
+use std::collections::BTreeMap;
+
+use map_to_javascript_html::{Catalog, MapToJavaScriptHTML};
+
+let mut en = BTreeMap::new();
+en.insert("hello", "Hello!");
+en.insert("bye", "Bye!");
+
+let mut fr = BTreeMap::new();
+fr.insert("hello", "Bonjour !");
+
+let mut catalog = Catalog::new("en");
+
+catalog.insert_locale("en", &en);
+catalog.insert_locale("fr", &fr);
+
+let text = catalog.localize("fr-CA").to_javascript_html("_text");
+
+assert_eq!("_text['bye']='Bye!';_text['hello']='Bonjour !';", text);
+```
+
+`Catalog::negotiate` chooses the locale for an HTTP `Accept-Language` header instead. The `negotiate_locale` function does the same for any list of available locales.
+
+```rust
+use map_to_javascript_html::negotiate_locale;
+
+let negotiation = negotiate_locale("fr-CA, fr;q=0.9, en;q=0.8", &["en", "fr"], "en");
+
+assert_eq!("fr", negotiation.locale);
+assert_eq!(vec!["fr", "en"], negotiation.fallback_chain);
+```
+
+## Layers
+
+`Layered` merges several maps, such as a global map, a feature map and a map of overrides for a page, into one map, so every key is assigned only once. A key which is in more than one layer is resolved by a `ConflictPolicy`: the first layer wins, the last layer wins, or merging fails. Each layer can have a prefix for its keys.
+
+```rust
+use std::collections::BTreeMap;
+
+use map_to_javascript_html::{ConflictPolicy, Layered, MapToJavaScriptHTML};
+
+let mut global = BTreeMap::new();
+global.insert("title", "My Website");
+
+let mut page = BTreeMap::new();
+page.insert("title", "Settings");
+
+let mut layered = Layered::new(ConflictPolicy::Error);
+
+layered.push(&global);
+layered.push_with_prefix("page.", &page);
+
+let text = layered.merge().unwrap().to_javascript_html("_text");
+
+assert_eq!("_text['page.title']='Settings';_text['title']='My Website';", text);
+```
+
+## Plural Messages
+
+`PluralMessages` writes messages which have variants for plural categories as functions taking a number. The plural rules of the locale are embedded from CLDR and compiled into the functions, so no i18n library is needed in the browser. Every `#` in the selected variant is replaced with the number.
+
+```rust
+use map_to_javascript_html::{MapToJavaScriptHTML, PluralCategory, PluralMessages};
+
+let mut messages = PluralMessages::new("ru");
+
+messages.insert("files", [
+    (PluralCategory::One, "# файл"),
+    (PluralCategory::Few, "# файла"),
+    (PluralCategory::Many, "# файлов"),
+]);
+
+let text = messages.to_javascript_html("_text");
+
+assert!(text.starts_with("_text['files']=function(n){"));
+```
+
+## Renderer
+
+A `Renderer` renders any map implementing the `JavaScriptEntries` trait with options. With the default options, its output is the same as the output of `MapToJavaScriptHTML`.
+
+The `placeholders` option compiles the string values which contain placeholders, such as `{name}` or `%s`, into JavaScript functions. The literal parts are escaped in the same way as other string values, and the placeholder names must be JavaScript identifiers.
+
+```rust
+use std::collections::BTreeMap;
+
+use map_to_javascript_html::{PlaceholderSyntax, Renderer};
+
+let mut map = BTreeMap::new();
+
+map.insert("hello", "Hello {name}!");
+map.insert("welcome", "Welcome to my website.");
+
+let text = Renderer::new().placeholders(PlaceholderSyntax::Braces).render(&map, "_text").unwrap();
+
+assert_eq!("_text['hello']=function(p){return 'Hello '+p.name+'!';};_text['welcome']='Welcome to my website.';", text);
+```
+
+`ScriptBuilder` writes several variables and their maps into one fragment, optionally declaring every variable and wrapping the fragment in an immediately invoked function expression.
+
+```rust
+use std::collections::BTreeMap;
+
+use map_to_javascript_html::{Declaration, ScriptBuilder};
+
+let mut text = BTreeMap::new();
+text.insert("hello", "Hello!");
+
+let mut config = BTreeMap::new();
+config.insert("theme", "dark");
+
+let script = ScriptBuilder::new()
+    .declaration(Declaration::Var)
+    .variable("_text", &text)
+    .variable("_config", &config)
+    .build()
+    .unwrap();
+
+assert_eq!("var _text={};_text['hello']='Hello!';var _config={};_config['theme']='dark';", script);
+```
+
+With the `namespaces` option, a variable name can be a dotted path, such as `window.App.i18n.messages`, and every missing level of the path is initialized to an empty object before the entries.
+
+With the `merge` option, every fragment merges its entries into the existing object, so several fragments contributing to `_text` can be included alone or in any order, and `var _text = {};` is no longer needed. Together with the `object_literal` option, the entries are merged by `Object.assign`. Read-only properties defined by `Immutability::DefineProperty` cannot be defined again by another fragment, so this combination fails with `RenderError::ReadOnlyMerge`.
+
+```rust
+use std::collections::BTreeMap;
+
+use map_to_javascript_html::ScriptBuilder;
+
+let mut text = BTreeMap::new();
+text.insert("hello", "Hello!");
+
+let script = ScriptBuilder::new().object_literal(true).merge(true).variable("_text", &text).build().unwrap();
+
+assert_eq!("var _text=Object.assign(_text||{},{'hello':'Hello!'});", script);
+```
+
+The `immutable` option of a `Renderer` protects the output from other scripts on the page. `Immutability::Freeze` wraps the objects in `Object.freeze`, including the arrays and objects in the values, and `Immutability::DefineProperty` defines every entry as a read-only property by `Object.defineProperty`. Both work with the entries assigned one by one and with the `object_literal` option of a `ScriptBuilder`.
+
+If the keys come from untrusted sources, the `unsafe_keys` option handles the keys which can pollute prototypes, such as `__proto__`, including the keys of nested objects. An `UnsafeKeyPolicy` rejects, renames or skips them, or creates the objects by `Object.create(null)` so that the keys are only own properties. The object of the variable itself is only created that way by a `ScriptBuilder` which declares the variable without merging into an existing object, unless the object is copied to be frozen, so otherwise the unsafe keys of the entries are rejected.
+
+```rust
+use std::collections::BTreeMap;
+
+use map_to_javascript_html::{Renderer, UnsafeKeyPolicy};
+
+let mut map = BTreeMap::new();
+
+map.insert("__proto__", "polluted");
+map.insert("hello", "Hello!");
+
+let text = Renderer::new().unsafe_keys(UnsafeKeyPolicy::Skip).render(&map, "_text").unwrap();
+
+assert_eq!("_text['hello']='Hello!';", text);
+```
+
+The `detect_collisions` option makes rendering fail if more than one entry has the same key, such as two keys of a map with the same `Display` output, or a key given twice to the methods with keys. Otherwise, the key is assigned more than once and the last value wins.
+
+To keep secrets out of the page, the `redact` option drops or masks the entries whose keys match a deny-list of patterns, such as `*password*` or `*token*`, including the entries of nested objects. In the strict mode, rendering also fails if a value looks like a random token.
+
+```rust
+use std::collections::BTreeMap;
+
+use map_to_javascript_html::{Redaction, Renderer};
+
+let mut config = BTreeMap::new();
+
+config.insert("apiToken", "sk_live_abc123");
+config.insert("theme", "dark");
+
+let text = Renderer::new().redact(Redaction::sensitive().strict(true)).render(&config, "_config").unwrap();
+
+assert_eq!("_config['theme']='dark';", text);
+```
+
+For pages which go through systems that cannot keep non-ASCII bytes, the `ascii_only` option escapes every non-ASCII character as `\uXXXX`, with surrogate pairs for the characters outside the Basic Multilingual Plane, so the output is pure 7-bit ASCII.
+
+```rust
+use std::collections::BTreeMap;
+
+use map_to_javascript_html::Renderer;
+
+let mut map = BTreeMap::new();
+
+map.insert("hello", "你好 😀");
+
+let text = Renderer::new().ascii_only(true).render(&map, "_text").unwrap();
+
+assert_eq!(r"_text['hello']='\u4f60\u597d \ud83d\ude00';", text);
+```
+
+The `minify` option shrinks the output further. Keys which are identifiers are written in dot notation, keys which are array indices are written as numbers, and each string is quoted by `'` or `"`, whichever needs fewer escapes. The output stays safe in HTML.
+
+```rust
+use std::collections::BTreeMap;
+
+use map_to_javascript_html::Renderer;
+
+let mut map = BTreeMap::new();
+
+map.insert("hello", "It's");
+map.insert("1", "One");
+map.insert("a-b", "</script>");
+
+let text = Renderer::new().minify(true).render(&map, "_text").unwrap();
+
+assert_eq!(r#"_text[1]='One';_text['a-b']='<\/script>';_text.hello="It's";"#, text);
+```
+
+For debugging and committing rendered catalogs to a repository, the `pretty` option writes every statement on its own line, including a line feed after the last one, and the elements of nested arrays and objects on their own lines, indented by spaces or tabs.
+
+```rust
+use std::collections::BTreeMap;
+
+use map_to_javascript_html::{Declaration, Indentation, Renderer, ScriptBuilder};
+
+let mut map = BTreeMap::new();
+
+map.insert("hello", "Hello!");
+map.insert("bye", "Bye!");
+
+let renderer = Renderer::new().pretty(Indentation::Spaces(2));
+
+assert_eq!("_text['bye'] = 'Bye!';\n_text['hello'] = 'Hello!';\n", renderer.render(&map, "_text").unwrap());
+
+let text = ScriptBuilder::new()
+    .declaration(Declaration::Const)
+    .object_literal(true)
+    .renderer(renderer)
+    .variable("_text", &map)
+    .build()
+    .unwrap();
+
+assert_eq!("const _text={\n  'bye': 'Bye!',\n  'hello': 'Hello!'\n};\n", text);
+```
+
+When the same long strings are used by many entries, such as legal notices, the `deduplicate_strings` option writes each of them only once. The entries are wrapped in a function which takes an array of the strings used more than once, and reference them by their indices. Strings shorter than the given length are kept as they are, because their references would not be shorter, and nothing is wrapped if the wrapper would make the output larger.
+
+```rust
+use std::collections::BTreeMap;
+
+use map_to_javascript_html::Renderer;
+
+let mut map = BTreeMap::new();
+
+map.insert("a", "Read the terms before continuing.");
+map.insert("b", "Read the terms before continuing.");
+map.insert("c", "OK");
+map.insert("d", "OK");
+
+let text = Renderer::new().deduplicate_strings(5).render(&map, "_text").unwrap();
+
+assert_eq!(
+    "(function(s){_text['a']=s[0];_text['b']=s[0];_text['c']='OK';_text['d']='OK';})(['Read the terms before continuing.']);",
+    text
+);
+```
+
+To keep a runaway map from producing a huge inline script, the `size_limit` option caps the bytes written by rendering a map. With `SizeLimitPolicy::Reject`, rendering fails with `RenderError::SizeLimitExceeded` before anything is written. With `SizeLimitPolicy::Truncate`, the output stops at an entry boundary, and the `_with_dropped_keys` methods, such as `render_to_vec_with_dropped_keys` and `render_with_keys_to_writer_with_dropped_keys`, return the keys of the dropped entries. A `ScriptBuilder` applies the limit to each variable separately and fails with `RenderError::TruncatedScript` instead of truncating, because it cannot return the dropped keys. The `max_value_length` option truncates each string value which has more characters than the given length, ending it with `…`, where an escape sequence such as `\\n` counts as one character.
+
+```rust
+use std::collections::BTreeMap;
+
+use map_to_javascript_html::{Renderer, SizeLimitPolicy};
+
+let mut map = BTreeMap::new();
+
+map.insert("a", "Hello, world!");
+map.insert("b", "Bye!");
+
+let renderer = Renderer::new().size_limit(30, SizeLimitPolicy::Truncate).max_value_length(6);
+
+let mut output = Vec::new();
+
+let dropped = renderer.render_to_vec_with_dropped_keys(&map, "_text", &mut output).unwrap();
+
+assert_eq!("_text['a']='Hello…';", String::from_utf8(output).unwrap());
+assert_eq!(vec![String::from("b")], dropped);
+```
+
+## Safety
+
+Keys and values are written as single-quoted JavaScript strings. `'`, line feeds and carriage returns are escaped, `</script`, `<script` and `<!--` are written as `<\/script`, `<\script` and `<\!--`, and a trailing backslash is doubled so that it cannot escape the closing quote. Other backslashes start escape sequences, so `\'` and `\n` are kept.
+
+Control characters are escaped in keys and values, including the nested strings of arrays and objects. Tabs, line feeds, carriage returns, backspaces and form feeds are written as `\t`, `\n`, `\r`, `\b` and `\f`, and other C0 and C1 control characters, `DEL`, and the line separators `U+2028` and `U+2029` are written as `\uXXXX`. With the `control_characters` option of a `Renderer`, they can be stripped or rejected instead. Tabs, line feeds and carriage returns are kept as ordinary text by the policy.
+
+```rust
+use std::collections::BTreeMap;
+
+use map_to_javascript_html::{ControlCharacterPolicy, MapToJavaScriptHTML, Renderer};
+
+let mut map = BTreeMap::new();
+
+map.insert("a", "b\0c\u{2028}");
+
+assert_eq!(r"_text['a']='b\u0000c\u2028';", map.to_javascript_html("_text"));
+
+let text = Renderer::new().control_characters(ControlCharacterPolicy::Strip).render(&map, "_text").unwrap();
+
+assert_eq!("_text['a']='bc';", text);
+```
+
+The `verify_script_safe` function checks that a fragment written by the `MapToJavaScriptHTML` trait contains no `</script`, `<script` or `<!--`, and that it only consists of the statements `_text['key']=value;` whose values are closed literals, so a text which breaks out of a string literal is caught. It only accepts this grammar, not the other output modes of a `Renderer`.
+
+```rust
+use std::collections::BTreeMap;
+
+use map_to_javascript_html::{verify_script_safe, MapToJavaScriptHTML};
+
+let mut map = BTreeMap::new();
+
+map.insert("a", "It's </script>\\");
+
+let text = map.to_javascript_html("_text");
+
+assert_eq!(r"_text['a']='It\'s <\/script>\\';", text);
+assert!(verify_script_safe(text.as_bytes(), "_text").is_ok());
+```
+
+The property tests in `tests/safety.rs` and the fuzz targets in the `fuzz` directory, which can be run by `cargo fuzz run maps`, check every map with arbitrary keys and values against it. The other output modes of a `Renderer` are run in Boa by the property tests in `tests/engine.rs`.
+
+With the `engine` feature, the `evaluate_javascript_html` and `verify_javascript_html` functions run the output in [Boa](https://boajs.dev/), an embedded JavaScript engine, so tests can check it against real JavaScript semantics instead of comparing strings. The feature is meant for tests, such as through `[dev-dependencies]`. `boa_engine` 0.18 does not compile with `intrusive-collections` 0.9.7, which a fresh resolve picks, so a project which enables it needs to pin the older version in its lockfile by `cargo update -p intrusive-collections --precise 0.9.6`.
+
+```rust
+# #[cfg(feature = "boa_engine")]
+# {
+use std::collections::BTreeMap;
+
+use map_to_javascript_html::{verify_javascript_html, MapToJavaScriptHTML};
+
+let mut map = BTreeMap::new();
+
+map.insert("hello", "It's </script>");
+
+let code = format!("var _text={{}};{}", map.to_javascript_html("_text"));
+
+assert!(verify_javascript_html(&map, &code, "_text").is_ok());
+# }
+```
+
 ## No Std
 
 Disable the default features to compile this crate without std.
@@ -82,6 +458,74 @@ To support the maps from the `serde` framework, enable the `serde` feature.
 version = "*"
 features = ["serde"]
 ```
+
+## Fluent Support
+
+To load [Project Fluent](https://projectfluent.org/) resources, enable the `fluent` feature. The `FluentMessages` type writes the messages of a `.ftl` resource either as their raw patterns or as small JavaScript functions.
+
+```toml
+[dependencies.map-to-javascript-html]
+version = "*"
+features = ["fluent"]
+```
+
+```rust
+# #[cfg(feature = "fluent-syntax")]
+# {
+use map_to_javascript_html::{FluentMessages, FluentOutput, MapToJavaScriptHTML};
+
+let messages = FluentMessages::parse("welcome = Hello, { $name }!", "en", FluentOutput::Functions).unwrap();
+
+let text = messages.to_javascript_html("_text");
+
+assert_eq!("_text['welcome']=function(p){p=p||{};return 'Hello, '+p.name+'!';};", text);
+# }
+```
+
+Then, the client can call `_text['welcome']({name: 'Magic Len'})`.
+
+## TOML and YAML Support
+
+To support `toml::Table` and `serde_yaml::Mapping`, enable the `toml` and `yaml` features respectively.
+
+```toml
+[dependencies.map-to-javascript-html]
+version = "*"
+features = ["toml", "yaml"]
+```
+
+TOML datetimes are written as strings in the RFC 3339 format. YAML keys which are null, booleans or numbers are converted to the property names JavaScript would use for them. Sequence and mapping keys are written as compact JSON texts, such as `["a","b"]`, instead of JavaScript's `a,b` and `[object Object]`, so that different keys stay different. YAML tags are ignored.
+
+## Hot Reload
+
+To reload the catalogs of a directory when its files change, enable the `watch` feature. A `CatalogWatcher` renders the output of every locale when loading, and swaps it atomically after a reload, so every request gets a cheap `Arc<[u8]>` snapshot.
+
+```toml
+[dependencies.map-to-javascript-html]
+version = "*"
+features = ["watch"]
+```
+
+```rust
+# #[cfg(feature = "notify")]
+# {
+use map_to_javascript_html::CatalogWatcher;
+
+# let locales = std::env::temp_dir().join(format!("map-to-javascript-html-doc-{}", std::process::id()));
+# std::fs::create_dir_all(&locales).unwrap();
+# std::fs::write(locales.join("en.properties"), "hello=Hello!\n").unwrap();
+# std::fs::write(locales.join("fr.properties"), "hello=Bonjour !\n").unwrap();
+let watcher = CatalogWatcher::new(&locales, "en", "_text").unwrap();
+
+let script = watcher.negotiate("fr-CA, fr;q=0.9, en;q=0.8");
+
+assert_eq!(b"_text['hello']='Bonjour !';", &*script);
+# drop(watcher);
+# std::fs::remove_dir_all(&locales).unwrap();
+# }
+```
+
+Changes are coalesced, and the directory is reloaded once it has been quiet for 100 milliseconds, so a file which an editor truncates and then rewrites is not loaded half-written. If a changed file cannot be loaded, the previous output is kept and the error can be taken by `CatalogWatcher::take_error` until a later reload succeeds.
 */
 
 #![cfg_attr(not(feature = "std"), no_std)]
@@ -89,8 +533,30 @@ features = ["serde"]
 #[macro_use]
 extern crate alloc;
 
+mod catalog;
+#[cfg(feature = "boa_engine")]
+mod engine;
+mod escape;
+mod function;
+mod json_text;
+mod layered;
+mod loaders;
 mod maps;
+mod plural;
+mod renderer;
 mod traits;
-
+mod value;
+mod verify;
+
+pub use catalog::*;
+#[cfg(feature = "boa_engine")]
+pub use engine::*;
+pub use layered::*;
+pub use loaders::*;
+#[allow(unused_imports)]
 pub use maps::*;
+pub use plural::*;
+pub use renderer::*;
 pub use traits::*;
+pub use value::*;
+pub use verify::*;
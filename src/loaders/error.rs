@@ -0,0 +1,96 @@
+use core::fmt::{self, Display, Formatter};
+#[cfg(feature = "std")]
+use std::error::Error;
+
+/// The kinds of errors which can occur when parsing a catalog.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseErrorKind {
+    /// A `\u` escape is not followed by four hexadecimal digits or does not form a valid Unicode scalar value.
+    InvalidUnicodeEscape,
+    /// An escape sequence is not supported.
+    InvalidEscape,
+    /// A quoted string is not terminated on its line.
+    UnterminatedString,
+    /// A quoted string is expected.
+    ExpectedString,
+    /// A keyword is unknown or appears where it is not allowed.
+    UnexpectedKeyword,
+    /// A continuation string does not follow a keyword.
+    UnexpectedString,
+    /// Extra characters follow a quoted string.
+    TrailingCharacters,
+    /// The index of a `msgstr[n]` keyword is invalid.
+    InvalidPluralIndex,
+    /// An entry has no `msgid`.
+    MissingMsgid,
+    /// An entry has no `msgstr`.
+    MissingMsgstr,
+    /// The syntax is invalid.
+    InvalidSyntax,
+}
+
+impl Display for ParseErrorKind {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            ParseErrorKind::InvalidUnicodeEscape => f.write_str("invalid unicode escape"),
+            ParseErrorKind::InvalidEscape => f.write_str("invalid escape sequence"),
+            ParseErrorKind::UnterminatedString => f.write_str("unterminated string"),
+            ParseErrorKind::ExpectedString => f.write_str("expected a quoted string"),
+            ParseErrorKind::UnexpectedKeyword => f.write_str("unexpected keyword"),
+            ParseErrorKind::UnexpectedString => f.write_str("unexpected string"),
+            ParseErrorKind::TrailingCharacters => f.write_str("trailing characters"),
+            ParseErrorKind::InvalidPluralIndex => f.write_str("invalid plural index"),
+            ParseErrorKind::MissingMsgid => f.write_str("missing msgid"),
+            ParseErrorKind::MissingMsgstr => f.write_str("missing msgstr"),
+            ParseErrorKind::InvalidSyntax => f.write_str("invalid syntax"),
+        }
+    }
+}
+
+/// An error which occurs when parsing a catalog. The line and the column are 1-based, and the column is counted in characters.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub(crate) kind:   ParseErrorKind,
+    pub(crate) line:   usize,
+    pub(crate) column: usize,
+}
+
+impl ParseError {
+    #[inline]
+    pub(crate) const fn new(kind: ParseErrorKind, line: usize, column: usize) -> Self {
+        ParseError {
+            kind,
+            line,
+            column,
+        }
+    }
+
+    /// The kind of this error.
+    #[inline]
+    pub const fn kind(&self) -> &ParseErrorKind {
+        &self.kind
+    }
+
+    /// The line where this error occurs.
+    #[inline]
+    pub const fn line(&self) -> usize {
+        self.line
+    }
+
+    /// The column where this error occurs.
+    #[inline]
+    pub const fn column(&self) -> usize {
+        self.column
+    }
+}
+
+impl Display for ParseError {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{} at line {}, column {}", self.kind, self.line, self.column)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for ParseError {}
@@ -0,0 +1,627 @@
+use alloc::{
+    borrow::{Cow, ToOwned},
+    boxed::Box,
+    collections::BTreeMap,
+    string::String,
+    vec::Vec,
+};
+use core::{borrow::Borrow, fmt::Display, hash::Hash};
+#[cfg(feature = "std")]
+use std::io::{self, Write};
+
+use fluent_syntax::{
+    ast::{self, InlineExpression, Pattern, PatternElement},
+    parser,
+    unicode::unescape_unicode_to_string,
+};
+
+use super::{ParseError, ParseErrorKind};
+use crate::{
+    escape,
+    function::{compile_function, escape_text, Expression, VariantKey},
+    renderer::write_value_to_vec,
+    JavaScriptEntries, JsValue, MapToJavaScriptHTML,
+};
+
+/// How the messages of a Fluent resource are written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FluentOutput {
+    /// Write the patterns of the messages as strings in the Fluent syntax, such as `'Hello, { $name }!'`.
+    Patterns,
+    /// Write every message as a function which takes an object of variables and returns the formatted string, such as `function(p){p=p||{};return 'Hello, '+p.name+'!';}`.
+    ///
+    /// Message references and term references are resolved when compiling. Functions such as `NUMBER` are not applied and their first positional argument is used. Plural variants are selected by the CLDR plural rules embedded for the locale, the same as `PluralRules`, so `Intl.PluralRules` is not needed.
+    Functions,
+}
+
+/// Messages loaded from a Fluent (`.ftl`) resource.
+///
+/// Each message is keyed by its identifier, and each attribute of a message is keyed by `message.attribute`. Terms are not written.
+#[derive(Debug, Clone)]
+pub struct FluentMessages {
+    /// The values and their JavaScript expressions which are safe to be used in HTML.
+    messages: BTreeMap<String, (JsValue<'static>, String)>,
+}
+
+type Message<'s> = ast::Message<&'s str>;
+type Term<'s> = ast::Term<&'s str>;
+
+struct Compiler<'s> {
+    messages: BTreeMap<&'s str, &'s Message<'s>>,
+    terms:    BTreeMap<&'s str, &'s Term<'s>>,
+    /// The messages and terms being compiled, which are used to detect cyclic references.
+    stack:    Vec<(bool, &'s str, Option<&'s str>)>,
+}
+
+impl<'s> Compiler<'s> {
+    fn compile_pattern(
+        &mut self,
+        pattern: &'s Pattern<&'s str>,
+        arguments: Option<&BTreeMap<&'s str, Expression>>,
+    ) -> Expression {
+        let mut expressions = Vec::with_capacity(pattern.elements.len());
+
+        for element in pattern.elements.iter() {
+            match element {
+                PatternElement::TextElement {
+                    value,
+                } => expressions.push(Expression::Text((*value).to_owned())),
+                PatternElement::Placeable {
+                    expression,
+                } => expressions.push(self.compile_expression(expression, arguments)),
+            }
+        }
+
+        Expression::Concat(expressions)
+    }
+
+    fn compile_expression(
+        &mut self,
+        expression: &'s ast::Expression<&'s str>,
+        arguments: Option<&BTreeMap<&'s str, Expression>>,
+    ) -> Expression {
+        match expression {
+            ast::Expression::Inline(expression) => self.compile_inline(expression, arguments),
+            ast::Expression::Select {
+                selector,
+                variants,
+            } => {
+                let selector = self.compile_inline(selector, arguments);
+
+                let mut default = None;
+                let mut compiled_variants = Vec::with_capacity(variants.len());
+
+                for variant in variants.iter() {
+                    let key = match &variant.key {
+                        ast::VariantKey::Identifier {
+                            name,
+                        } => VariantKey::Name((*name).to_owned()),
+                        ast::VariantKey::NumberLiteral {
+                            value,
+                        } => VariantKey::Number((*value).to_owned()),
+                    };
+
+                    let value = self.compile_pattern(&variant.value, arguments);
+
+                    if variant.default {
+                        default = Some(value.clone());
+                    }
+
+                    compiled_variants.push((key, value));
+                }
+
+                Expression::Select {
+                    selector: selector.into(),
+                    variants: compiled_variants,
+                    default:  default.unwrap_or_else(|| Expression::Text(String::new())).into(),
+                }
+            },
+        }
+    }
+
+    fn compile_reference(
+        &mut self,
+        is_term: bool,
+        id: &'s str,
+        attribute: Option<&'s str>,
+        arguments: Option<&BTreeMap<&'s str, Expression>>,
+    ) -> Expression {
+        let fallback = || {
+            let prefix = if is_term { "-" } else { "" };
+
+            match attribute {
+                Some(attribute) => Expression::Text(format!("{{{}{}.{}}}", prefix, id, attribute)),
+                None => Expression::Text(format!("{{{}{}}}", prefix, id)),
+            }
+        };
+
+        if self.stack.contains(&(is_term, id, attribute)) {
+            return fallback();
+        }
+
+        let (value, attributes) = if is_term {
+            match self.terms.get(id) {
+                Some(term) => (Some(&term.value), &term.attributes),
+                None => return fallback(),
+            }
+        } else {
+            match self.messages.get(id) {
+                Some(message) => (message.value.as_ref(), &message.attributes),
+                None => return fallback(),
+            }
+        };
+
+        let pattern = match attribute {
+            Some(attribute) => attributes.iter().find(|a| a.id.name == attribute).map(|a| &a.value),
+            None => value,
+        };
+
+        match pattern {
+            Some(pattern) => {
+                self.stack.push((is_term, id, attribute));
+
+                let expression = self.compile_pattern(pattern, arguments);
+
+                self.stack.pop();
+
+                expression
+            },
+            None => fallback(),
+        }
+    }
+
+    fn compile_inline(
+        &mut self,
+        expression: &'s InlineExpression<&'s str>,
+        arguments: Option<&BTreeMap<&'s str, Expression>>,
+    ) -> Expression {
+        match expression {
+            InlineExpression::StringLiteral {
+                value,
+            } => Expression::Text(unescape_unicode_to_string(value).into_owned()),
+            InlineExpression::NumberLiteral {
+                value,
+            } => Expression::Number((*value).to_owned()),
+            InlineExpression::FunctionReference {
+                id,
+                arguments: call_arguments,
+            } => match call_arguments.positional.first() {
+                Some(expression) => self.compile_inline(expression, arguments),
+                None => Expression::Text(format!("{{{}()}}", id.name)),
+            },
+            InlineExpression::MessageReference {
+                id,
+                attribute,
+            } => self.compile_reference(false, id.name, attribute.as_ref().map(|a| a.name), None),
+            InlineExpression::TermReference {
+                id,
+                attribute,
+                arguments: call_arguments,
+            } => {
+                let mut term_arguments = BTreeMap::new();
+
+                if let Some(call_arguments) = call_arguments {
+                    for argument in call_arguments.named.iter() {
+                        let value = self.compile_inline(&argument.value, arguments);
+
+                        term_arguments.insert(argument.name.name, value);
+                    }
+                }
+
+                self.compile_reference(
+                    true,
+                    id.name,
+                    attribute.as_ref().map(|a| a.name),
+                    Some(&term_arguments),
+                )
+            },
+            InlineExpression::VariableReference {
+                id,
+            } => match arguments {
+                Some(arguments) => match arguments.get(id.name) {
+                    Some(expression) => expression.clone(),
+                    None => Expression::Text(format!("{{${}}}", id.name)),
+                },
+                None => Expression::Parameter(id.name.to_owned()),
+            },
+            InlineExpression::Placeable {
+                expression,
+            } => self.compile_expression(expression, arguments),
+        }
+    }
+}
+
+fn serialize_inline(expression: &InlineExpression<&str>, output: &mut String) {
+    match expression {
+        InlineExpression::StringLiteral {
+            value,
+        } => {
+            output.push('"');
+            output.push_str(value);
+            output.push('"');
+        },
+        InlineExpression::NumberLiteral {
+            value,
+        } => output.push_str(value),
+        InlineExpression::FunctionReference {
+            id,
+            arguments,
+        } => {
+            output.push_str(id.name);
+            output.push('(');
+            serialize_call_arguments(arguments, output);
+            output.push(')');
+        },
+        InlineExpression::MessageReference {
+            id,
+            attribute,
+        } => {
+            output.push_str(id.name);
+
+            if let Some(attribute) = attribute {
+                output.push('.');
+                output.push_str(attribute.name);
+            }
+        },
+        InlineExpression::TermReference {
+            id,
+            attribute,
+            arguments,
+        } => {
+            output.push('-');
+            output.push_str(id.name);
+
+            if let Some(attribute) = attribute {
+                output.push('.');
+                output.push_str(attribute.name);
+            }
+
+            if let Some(arguments) = arguments {
+                output.push('(');
+                serialize_call_arguments(arguments, output);
+                output.push(')');
+            }
+        },
+        InlineExpression::VariableReference {
+            id,
+        } => {
+            output.push('$');
+            output.push_str(id.name);
+        },
+        InlineExpression::Placeable {
+            expression,
+        } => {
+            output.push_str("{ ");
+            serialize_expression(expression, output);
+            output.push_str(" }");
+        },
+    }
+}
+
+fn serialize_call_arguments(arguments: &ast::CallArguments<&str>, output: &mut String) {
+    let positional = arguments.positional.iter().map(|expression| (None, expression));
+    let named = arguments.named.iter().map(|argument| (Some(argument.name.name), &argument.value));
+
+    for (i, (name, expression)) in positional.chain(named).enumerate() {
+        if i > 0 {
+            output.push_str(", ");
+        }
+
+        if let Some(name) = name {
+            output.push_str(name);
+            output.push_str(": ");
+        }
+
+        serialize_inline(expression, output);
+    }
+}
+
+fn serialize_expression(expression: &ast::Expression<&str>, output: &mut String) {
+    match expression {
+        ast::Expression::Inline(expression) => serialize_inline(expression, output),
+        ast::Expression::Select {
+            selector,
+            variants,
+        } => {
+            serialize_inline(selector, output);
+            output.push_str(" ->");
+
+            for variant in variants.iter() {
+                output.push_str(if variant.default { "\n   *[" } else { "\n    [" });
+
+                match &variant.key {
+                    ast::VariantKey::Identifier {
+                        name,
+                    } => output.push_str(name),
+                    ast::VariantKey::NumberLiteral {
+                        value,
+                    } => output.push_str(value),
+                }
+
+                output.push_str("] ");
+
+                let mut pattern = String::new();
+
+                serialize_pattern(&variant.value, &mut pattern);
+
+                output.push_str(&pattern.replace('\n', "\n        "));
+            }
+
+            output.push('\n');
+        },
+    }
+}
+
+fn serialize_pattern(pattern: &Pattern<&str>, output: &mut String) {
+    for element in pattern.elements.iter() {
+        match element {
+            PatternElement::TextElement {
+                value,
+            } => output.push_str(value),
+            PatternElement::Placeable {
+                expression,
+            } => {
+                output.push_str("{ ");
+                serialize_expression(expression, output);
+                output.push_str(" }");
+            },
+        }
+    }
+}
+
+fn position_of(source: &str, index: usize) -> (usize, usize) {
+    let before = &source[..index.min(source.len())];
+
+    let line = before.matches('\n').count() + 1;
+    let column = before.rsplit('\n').next().unwrap().chars().count() + 1;
+
+    (line, column)
+}
+
+impl FluentMessages {
+    /// Parse a Fluent resource. `locale` is used to select plural variants when `output` is `FluentOutput::Functions`. If it is empty or unknown, only the `other` variant is selected by plural categories.
+    ///
+    /// If the resource has syntax errors, the first one is returned.
+    ///
+    /// ```
+    /// use map_to_javascript_html::{
+    ///     FluentMessages, FluentOutput, MapToJavaScriptHTML,
+    /// };
+    ///
+    /// let messages = FluentMessages::parse(
+    ///     "welcome = Hello, { $name }!",
+    ///     "en",
+    ///     FluentOutput::Functions,
+    /// )
+    /// .unwrap();
+    ///
+    /// assert_eq!(
+    ///     "_text['welcome']=function(p){p=p||{};return 'Hello, '+p.name+'!';};",
+    ///     messages.to_javascript_html("_text")
+    /// );
+    /// ```
+    pub fn parse<L: AsRef<str>>(
+        source: &str,
+        locale: L,
+        output: FluentOutput,
+    ) -> Result<FluentMessages, ParseError> {
+        let resource = parser::parse(source).map_err(|(_, errors)| {
+            let (line, column) = position_of(source, errors[0].pos.start);
+
+            ParseError::new(ParseErrorKind::InvalidSyntax, line, column)
+        })?;
+
+        let locale = locale.as_ref();
+
+        let mut compiler =
+            Compiler {
+                messages: BTreeMap::new(), terms: BTreeMap::new(), stack: Vec::new()
+            };
+
+        for entry in resource.body.iter() {
+            match entry {
+                ast::Entry::Message(message) => {
+                    compiler.messages.insert(message.id.name, message);
+                },
+                ast::Entry::Term(term) => {
+                    compiler.terms.insert(term.id.name, term);
+                },
+                _ => (),
+            }
+        }
+
+        let mut entries = Vec::new();
+
+        for message in compiler.messages.values() {
+            if let Some(value) = message.value.as_ref() {
+                entries.push((message.id.name, None, value));
+            }
+
+            for attribute in message.attributes.iter() {
+                entries.push((message.id.name, Some(attribute.id.name), &attribute.value));
+            }
+        }
+
+        let mut messages = BTreeMap::new();
+
+        for (id, attribute, pattern) in entries {
+            let value = match output {
+                FluentOutput::Patterns => {
+                    let mut s = String::new();
+
+                    serialize_pattern(pattern, &mut s);
+
+                    JsValue::String(Cow::Owned(escape_text(&s)))
+                },
+                FluentOutput::Functions => {
+                    let expression = compiler.compile_reference(false, id, attribute, None);
+
+                    JsValue::Function(compile_function(expression, locale, true))
+                },
+            };
+
+            let mut code = Vec::new();
+
+            write_value_to_vec(&value, &mut code);
+
+            let key = match attribute {
+                Some(attribute) => format!("{}.{}", id, attribute),
+                None => id.to_owned(),
+            };
+
+            // the code only contains valid UTF-8 strings and ASCII characters
+            messages.insert(key, (value, unsafe { String::from_utf8_unchecked(code) }));
+        }
+
+        Ok(FluentMessages {
+            messages,
+        })
+    }
+
+    /// The number of the written entries, including the attributes of messages.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    /// Whether there is no entry.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+
+    /// Get the JavaScript expression of an entry.
+    #[inline]
+    pub fn get<K: AsRef<str>>(&self, key: K) -> Option<&str> {
+        self.messages.get(key.as_ref()).map(|(_, code)| code.as_str())
+    }
+}
+
+impl MapToJavaScriptHTML<String> for FluentMessages {
+    fn to_javascript_html_to_vec<'a, S: Display>(
+        &self,
+        variable_name: S,
+        output: &'a mut Vec<u8>,
+    ) -> &'a [u8] {
+        let variable_name = format!("{}", variable_name);
+
+        let current_length = output.len();
+
+        output.reserve((variable_name.len() + 11) * self.messages.len());
+
+        for (key, (_, code)) in self.messages.iter() {
+            output.extend_from_slice(variable_name.as_bytes());
+            output.extend_from_slice(b"['");
+            escape::encode_script_single_quoted_text_to_vec(key, output);
+            output.extend_from_slice(b"']=");
+            output.extend_from_slice(code.as_bytes());
+            output.push(b';');
+        }
+
+        &output[current_length..]
+    }
+
+    #[cfg(feature = "std")]
+    fn to_javascript_html_to_writer<S: Display, W: Write>(
+        &self,
+        variable_name: S,
+        output: &mut W,
+    ) -> Result<(), io::Error> {
+        let variable_name = format!("{}", variable_name);
+
+        for (key, (_, code)) in self.messages.iter() {
+            output.write_all(variable_name.as_bytes())?;
+            output.write_all(b"['")?;
+            escape::encode_script_single_quoted_text_to_writer(key, output)?;
+            output.write_all(b"']=")?;
+            output.write_all(code.as_bytes())?;
+            output.write_all(b";")?;
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    fn to_javascript_html_with_keys_to_vec<'a, S: Display, KS: ?Sized + Display + Ord + Hash>(
+        &self,
+        variable_name: S,
+        keys: &[&KS],
+        output: &'a mut Vec<u8>,
+    ) -> &'a [u8]
+    where
+        String: Borrow<KS>, {
+        let variable_name = format!("{}", variable_name);
+
+        let current_length = output.len();
+
+        output.reserve((variable_name.len() + 11) * keys.len());
+
+        for key in keys.iter() {
+            output.extend_from_slice(variable_name.as_bytes());
+            output.extend_from_slice(b"['");
+            escape::encode_script_single_quoted_text_to_vec(format!("{}", key), output);
+            output.extend_from_slice(b"']=");
+            match self.messages.get(key) {
+                Some((_, code)) => {
+                    output.extend_from_slice(code.as_bytes());
+                    output.push(b';');
+                },
+                None => {
+                    output.extend_from_slice(b"undefined;");
+                },
+            }
+        }
+
+        &output[current_length..]
+    }
+
+    #[cfg(feature = "std")]
+    fn to_javascript_html_with_keys_to_writer<
+        S: Display,
+        W: Write,
+        KS: ?Sized + Display + Ord + Hash,
+    >(
+        &self,
+        variable_name: S,
+        keys: &[&KS],
+        output: &mut W,
+    ) -> Result<(), io::Error>
+    where
+        String: Borrow<KS>, {
+        let variable_name = format!("{}", variable_name);
+
+        for key in keys.iter() {
+            output.write_all(variable_name.as_bytes())?;
+            output.write_all(b"['")?;
+            escape::encode_script_single_quoted_text_to_writer(format!("{}", key), output)?;
+            output.write_all(b"']=")?;
+            match self.messages.get(key) {
+                Some((_, code)) => {
+                    output.write_all(code.as_bytes())?;
+                    output.write_all(b";")?;
+                },
+                None => {
+                    output.write_all(b"undefined;")?;
+                },
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl JavaScriptEntries for FluentMessages {
+    #[inline]
+    fn javascript_entries(&self) -> Box<dyn Iterator<Item = (Cow<'_, str>, JsValue<'_>)> + '_> {
+        Box::new(
+            self.messages
+                .iter()
+                .map(|(key, (value, _))| (Cow::Borrowed(key.as_str()), value.clone())),
+        )
+    }
+
+    #[inline]
+    fn javascript_entry(&self, key: &str) -> Option<JsValue<'_>> {
+        self.messages.get(key).map(|(value, _)| value.clone())
+    }
+}
@@ -0,0 +1,486 @@
+use alloc::{borrow::Cow, string::String, vec::Vec};
+
+use super::{ParseError, ParseErrorKind};
+use crate::{JsFunction, JsNumber, JsValue};
+
+/// A parser of the JavaScript code written by this crate.
+struct Parser<'a> {
+    text:                  &'a str,
+    bytes:                 &'a [u8],
+    p:                     usize,
+    /// Only accept the functions written for the placeholders of a `Renderer`, instead of keeping any function as it is.
+    placeholder_functions: bool,
+}
+
+impl<'a> Parser<'a> {
+    #[inline]
+    fn new(text: &'a str, placeholder_functions: bool) -> Self {
+        Parser {
+            text,
+            bytes: text.as_bytes(),
+            p: 0,
+            placeholder_functions,
+        }
+    }
+
+    /// Create an error at a byte index.
+    fn error_at(&self, kind: ParseErrorKind, index: usize) -> ParseError {
+        let before = &self.text[..index];
+
+        let line_start = before.rfind(['\n', '\r']).map_or(0, |i| i + 1);
+        let line = before.matches('\n').count() + before.matches('\r').count()
+            - before.matches("\r\n").count()
+            + 1;
+        let column = before[line_start..].chars().count() + 1;
+
+        ParseError::new(kind, line, column)
+    }
+
+    #[inline]
+    fn error(&self, kind: ParseErrorKind) -> ParseError {
+        self.error_at(kind, self.p)
+    }
+
+    #[inline]
+    fn skip_whitespace(&mut self) {
+        while self.p < self.bytes.len() && self.bytes[self.p].is_ascii_whitespace() {
+            self.p += 1;
+        }
+    }
+
+    #[inline]
+    fn eat(&mut self, s: &str) -> bool {
+        if self.text[self.p..].starts_with(s) {
+            self.p += s.len();
+
+            true
+        } else {
+            false
+        }
+    }
+
+    #[inline]
+    fn expect(&mut self, s: &str) -> Result<(), ParseError> {
+        if self.eat(s) {
+            Ok(())
+        } else {
+            Err(self.error(ParseErrorKind::InvalidSyntax))
+        }
+    }
+
+    fn read_hex(&mut self, digits: usize, start: usize) -> Result<u32, ParseError> {
+        let hex = self
+            .text
+            .get(self.p..self.p + digits)
+            .filter(|hex| hex.bytes().all(|b| b.is_ascii_hexdigit()))
+            .ok_or_else(|| self.error_at(ParseErrorKind::InvalidUnicodeEscape, start))?;
+
+        self.p += digits;
+
+        Ok(u32::from_str_radix(hex, 16).unwrap())
+    }
+
+    /// Read the code unit or the code point of a `\u` escape whose `u` has been read.
+    fn read_unicode_escape(&mut self, start: usize) -> Result<u32, ParseError> {
+        if self.eat("{") {
+            let digits = self.bytes[self.p..].iter().take_while(|b| b.is_ascii_hexdigit()).count();
+
+            // leading zeros are allowed
+            let zeros =
+                self.bytes[self.p..self.p + digits].iter().take_while(|b| **b == b'0').count();
+
+            if digits == 0 || digits - zeros > 6 {
+                return Err(self.error_at(ParseErrorKind::InvalidUnicodeEscape, start));
+            }
+
+            self.p += zeros;
+
+            let code_point =
+                if digits == zeros { 0 } else { self.read_hex(digits - zeros, start)? };
+
+            if !self.eat("}") {
+                return Err(self.error_at(ParseErrorKind::InvalidUnicodeEscape, start));
+            }
+
+            Ok(code_point)
+        } else {
+            self.read_hex(4, start)
+        }
+    }
+
+    /// Unescape the escape sequence whose backslash has been read.
+    fn unescape(&mut self, output: &mut String) -> Result<(), ParseError> {
+        let start = self.p - 1;
+
+        let c = match self.text[self.p..].chars().next() {
+            Some(c) => c,
+            None => return Err(self.error_at(ParseErrorKind::UnterminatedString, start)),
+        };
+
+        self.p += c.len_utf8();
+
+        match c {
+            'n' => output.push('\n'),
+            'r' => output.push('\r'),
+            't' => output.push('\t'),
+            'b' => output.push('\u{8}'),
+            'f' => output.push('\u{C}'),
+            'v' => output.push('\u{B}'),
+            // legacy octal escapes, which are allowed in scripts which are not strict
+            '0'..='7' => {
+                let max_digits = if c <= '3' { 2 } else { 1 };
+
+                let digits = self.bytes[self.p..]
+                    .iter()
+                    .take(max_digits)
+                    .take_while(|b| matches!(b, b'0'..=b'7'))
+                    .count();
+
+                let code_point =
+                    u32::from_str_radix(&self.text[start + 1..self.p + digits], 8).unwrap();
+
+                self.p += digits;
+
+                output.push(char::from_u32(code_point).unwrap());
+            },
+            'x' => {
+                let code_point = self
+                    .read_hex(2, start)
+                    .map_err(|_| self.error_at(ParseErrorKind::InvalidEscape, start))?;
+
+                output.push(char::from_u32(code_point).unwrap());
+            },
+            'u' => {
+                let mut code_point = self.read_unicode_escape(start)?;
+
+                if (0xD800..=0xDBFF).contains(&code_point) && self.eat("\\u") {
+                    let low = self.read_unicode_escape(start)?;
+
+                    if !(0xDC00..=0xDFFF).contains(&low) {
+                        return Err(self.error_at(ParseErrorKind::InvalidUnicodeEscape, start));
+                    }
+
+                    code_point = 0x10000 + ((code_point - 0xD800) << 10) + (low - 0xDC00);
+                }
+
+                output.push(
+                    char::from_u32(code_point).ok_or_else(|| {
+                        self.error_at(ParseErrorKind::InvalidUnicodeEscape, start)
+                    })?,
+                );
+            },
+            // line continuations
+            '\n' | '\u{2028}' | '\u{2029}' => (),
+            '\r' => {
+                self.eat("\n");
+            },
+            _ => output.push(c),
+        }
+
+        Ok(())
+    }
+
+    /// Read a string literal quoted by `'` or `"`.
+    fn read_string(&mut self) -> Result<String, ParseError> {
+        let start = self.p;
+
+        let quote = match self.bytes.get(self.p) {
+            Some(b'\'') => b'\'',
+            Some(b'"') => b'"',
+            _ => return Err(self.error(ParseErrorKind::ExpectedString)),
+        };
+
+        self.p += 1;
+
+        let mut output = String::new();
+
+        loop {
+            let end = self.bytes[self.p..]
+                .iter()
+                .position(|b| matches!(*b, b'\\' | b'\n' | b'\r') || *b == quote)
+                .map(|i| self.p + i);
+
+            let end = match end {
+                Some(end) if self.bytes[end] != b'\n' && self.bytes[end] != b'\r' => end,
+                _ => return Err(self.error_at(ParseErrorKind::UnterminatedString, start)),
+            };
+
+            output.push_str(&self.text[self.p..end]);
+
+            self.p = end + 1;
+
+            if self.bytes[end] == quote {
+                return Ok(output);
+            }
+
+            self.unescape(&mut output)?;
+        }
+    }
+
+    /// Skip a string literal and return whether there is one.
+    fn skip_string(&mut self) -> Result<bool, ParseError> {
+        if matches!(self.bytes.get(self.p), Some(b'\'' | b'"')) {
+            self.read_string()?;
+
+            Ok(true)
+        } else {
+            Ok(false)
+        }
+    }
+
+    /// Skip the code until the bracket which closes an opening bracket that has been read.
+    fn skip_brackets(&mut self) -> Result<(), ParseError> {
+        let mut depth = 1;
+
+        while depth > 0 {
+            if self.skip_string()? {
+                continue;
+            }
+
+            match self.bytes.get(self.p) {
+                Some(b'(' | b'[' | b'{') => depth += 1,
+                Some(b')' | b']' | b'}') => depth -= 1,
+                Some(_) => (),
+                None => return Err(self.error(ParseErrorKind::InvalidSyntax)),
+            }
+
+            self.p += 1;
+        }
+
+        Ok(())
+    }
+
+    /// Read a function expression, whose code is kept as it is.
+    fn read_function(&mut self) -> Result<JsValue<'static>, ParseError> {
+        let start = self.p;
+
+        self.expect("function(")?;
+        self.skip_brackets()?;
+        self.expect("{")?;
+        self.skip_brackets()?;
+
+        Ok(JsValue::Function(JsFunction {
+            code: String::from(&self.text[start..self.p])
+        }))
+    }
+
+    /// Read a function written for the placeholders of a `Renderer`, such as `function(p){return 'Hello, '+p.name+'!';}`, whose body only concatenates string literals and the properties of `p`.
+    fn read_placeholder_function(&mut self) -> Result<JsValue<'static>, ParseError> {
+        let start = self.p;
+
+        if !self.eat("function(){return ") {
+            self.expect("function(p){")?;
+            self.eat("p=p||{};");
+            self.expect("return ")?;
+        }
+
+        loop {
+            if self.eat("p.") {
+                let length = self.bytes[self.p..]
+                    .iter()
+                    .take_while(|b| b.is_ascii_alphanumeric() || matches!(b, b'_' | b'$'))
+                    .count();
+
+                if length == 0 || self.bytes[self.p].is_ascii_digit() {
+                    return Err(self.error(ParseErrorKind::InvalidSyntax));
+                }
+
+                self.p += length;
+            } else if self.eat("p[") {
+                if !self.skip_string()? {
+                    let length =
+                        self.bytes[self.p..].iter().take_while(|b| b.is_ascii_digit()).count();
+
+                    if length == 0 {
+                        return Err(self.error(ParseErrorKind::InvalidSyntax));
+                    }
+
+                    self.p += length;
+                }
+
+                self.expect("]")?;
+            } else {
+                self.read_string()?;
+            }
+
+            if !self.eat("+") {
+                break;
+            }
+        }
+
+        self.expect(";}")?;
+
+        Ok(JsValue::Function(JsFunction {
+            code: String::from(&self.text[start..self.p])
+        }))
+    }
+
+    fn read_number(&mut self) -> Result<JsValue<'static>, ParseError> {
+        let start = self.p;
+
+        if self.eat("-Infinity") || self.eat("Infinity") || self.eat("NaN") {
+            return Ok(JsValue::Number(JsNumber::parse(&self.text[start..self.p]).unwrap()));
+        }
+
+        let length = self.bytes[self.p..]
+            .iter()
+            .take_while(|b| b.is_ascii_digit() || matches!(b, b'-' | b'+' | b'.' | b'e' | b'E'))
+            .count();
+
+        self.p += length;
+
+        JsNumber::parse(&self.text[start..self.p])
+            .map(JsValue::Number)
+            .ok_or_else(|| self.error_at(ParseErrorKind::InvalidSyntax, start))
+    }
+
+    fn read_value(&mut self) -> Result<JsValue<'static>, ParseError> {
+        match self.bytes.get(self.p) {
+            Some(b'\'' | b'"') => Ok(JsValue::String(Cow::Owned(self.read_string()?))),
+            Some(b'[') => {
+                self.p += 1;
+
+                let mut array = Vec::new();
+
+                if !self.eat("]") {
+                    loop {
+                        array.push(self.read_value()?);
+
+                        if self.eat("]") {
+                            break;
+                        }
+
+                        self.expect(",")?;
+                    }
+                }
+
+                Ok(JsValue::Array(array))
+            },
+            Some(b'{') => {
+                self.p += 1;
+
+                let mut object = Vec::new();
+
+                if !self.eat("}") {
+                    loop {
+                        let key = self.read_string()?;
+
+                        self.expect(":")?;
+
+                        object.push((Cow::Owned(key), self.read_value()?));
+
+                        if self.eat("}") {
+                            break;
+                        }
+
+                        self.expect(",")?;
+                    }
+                }
+
+                Ok(JsValue::Object(object))
+            },
+            Some(b'-' | b'0'..=b'9' | b'I' | b'N') => self.read_number(),
+            Some(b'f') if self.text[self.p..].starts_with("function(") => {
+                if self.placeholder_functions {
+                    self.read_placeholder_function()
+                } else {
+                    self.read_function()
+                }
+            },
+            _ => {
+                for (keyword, value) in [
+                    ("undefined", JsValue::Undefined),
+                    ("null", JsValue::Null),
+                    ("true", JsValue::Bool(true)),
+                    ("false", JsValue::Bool(false)),
+                ] {
+                    if self.eat(keyword) {
+                        return Ok(value);
+                    }
+                }
+
+                Err(self.error(ParseErrorKind::InvalidSyntax))
+            },
+        }
+    }
+}
+
+/// Parse the JavaScript code written by the `MapToJavaScriptHTML` trait, such as `_text['hello']='Hello world!';`, back into its entries, in the order they are written.
+///
+/// Only the code which this crate writes is accepted. Every statement must assign a value to a key of `variable_name`, and whitespace can only be between the statements. The escape sequences of strings are unescaped as JavaScript does, so a backslash in a value which started an escape sequence when it was written is not read back. Arrays and objects are read from their JSON text, and functions are kept as they are.
+///
+/// ```
+/// use map_to_javascript_html::{parse_javascript_html, JsValue};
+///
+/// let entries = parse_javascript_html(
+///     r"_text['hello']='It\'s <\/script>';_text['n']=[1,null];",
+///     "_text",
+/// )
+/// .unwrap();
+///
+/// assert_eq!(
+///     vec![
+///         (String::from("hello"), JsValue::String("It's </script>".into())),
+///         (
+///             String::from("n"),
+///             JsValue::Array(vec![JsValue::Number(1.into()), JsValue::Null])
+///         ),
+///     ],
+///     entries
+/// );
+/// ```
+#[inline]
+pub fn parse_javascript_html(
+    text: &str,
+    variable_name: &str,
+) -> Result<Vec<(String, JsValue<'static>)>, ParseError> {
+    parse_statements(text, variable_name, false).map_err(|(_, error)| error)
+}
+
+/// Parse the statements like `parse_javascript_html`. If `placeholder_functions` is `true`, every function must be written for the placeholders of a `Renderer`. An error comes with the byte index of the statement where it occurs.
+pub(crate) fn parse_statements(
+    text: &str,
+    variable_name: &str,
+    placeholder_functions: bool,
+) -> Result<Vec<(String, JsValue<'static>)>, (usize, ParseError)> {
+    let mut parser = Parser::new(text, placeholder_functions);
+
+    let mut entries = Vec::new();
+
+    loop {
+        parser.skip_whitespace();
+
+        if parser.p == parser.bytes.len() {
+            break;
+        }
+
+        let start = parser.p;
+
+        let entry = parse_statement(&mut parser, variable_name).map_err(|error| (start, error))?;
+
+        entries.push(entry);
+    }
+
+    Ok(entries)
+}
+
+/// Parse a statement `variable_name['key']=value;`.
+fn parse_statement(
+    parser: &mut Parser,
+    variable_name: &str,
+) -> Result<(String, JsValue<'static>), ParseError> {
+    parser.expect(variable_name)?;
+    parser.expect("[")?;
+
+    let key = parser.read_string()?;
+
+    parser.expect("]=")?;
+
+    let value = parser.read_value()?;
+
+    if !parser.eat(";") {
+        return Err(parser.error(ParseErrorKind::TrailingCharacters));
+    }
+
+    Ok((key, value))
+}
@@ -0,0 +1,41 @@
+mod error;
+#[cfg(feature = "fluent-syntax")]
+mod fluent;
+mod javascript;
+mod po;
+mod properties;
+
+pub use error::*;
+#[cfg(feature = "fluent-syntax")]
+pub use fluent::*;
+pub use javascript::*;
+pub use po::*;
+pub use properties::*;
+
+/// Split a text into lines. `\n`, `\r` and `\r\n` are all treated as line terminators.
+fn split_lines(text: &str) -> impl Iterator<Item = &str> {
+    let mut rest = Some(text);
+
+    core::iter::from_fn(move || {
+        let text = rest?;
+
+        match text.find(['\n', '\r']) {
+            Some(index) => {
+                let next = if text[index..].starts_with("\r\n") { index + 2 } else { index + 1 };
+
+                rest = Some(&text[next..]);
+
+                Some(&text[..index])
+            },
+            None => {
+                rest = None;
+
+                if text.is_empty() {
+                    None
+                } else {
+                    Some(text)
+                }
+            },
+        }
+    })
+}
@@ -0,0 +1,338 @@
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+
+use super::{split_lines, ParseError, ParseErrorKind};
+
+/// The field which continuation strings are appended to.
+#[derive(Clone, Copy)]
+enum Field {
+    Context,
+    Id,
+    IdPlural,
+    Str(usize),
+}
+
+#[derive(Default)]
+struct Entry {
+    line:       usize,
+    fuzzy:      bool,
+    context:    Option<String>,
+    id:         Option<String>,
+    id_plural:  Option<String>,
+    strs:       Vec<(usize, String)>,
+    has_msgstr: bool,
+}
+
+impl Entry {
+    #[inline]
+    fn is_started(&self) -> bool {
+        self.context.is_some() || self.id.is_some()
+    }
+
+    fn field_mut(&mut self, field: Field) -> &mut String {
+        match field {
+            Field::Context => self.context.get_or_insert_with(String::new),
+            Field::Id => self.id.get_or_insert_with(String::new),
+            Field::IdPlural => self.id_plural.get_or_insert_with(String::new),
+            Field::Str(i) => &mut self.strs[i].1,
+        }
+    }
+
+    fn finish(&mut self, map: &mut BTreeMap<String, String>) -> Result<(), ParseError> {
+        let entry = core::mem::take(self);
+
+        if !entry.is_started() {
+            return Ok(());
+        }
+
+        let id = match entry.id {
+            Some(id) => id,
+            None => return Err(ParseError::new(ParseErrorKind::MissingMsgid, entry.line, 1)),
+        };
+
+        if !entry.has_msgstr {
+            return Err(ParseError::new(ParseErrorKind::MissingMsgstr, entry.line, 1));
+        }
+
+        // the header entry and fuzzy entries are not translations
+        if entry.fuzzy || (id.is_empty() && entry.context.is_none()) {
+            return Ok(());
+        }
+
+        let key = match entry.context {
+            Some(mut context) => {
+                context.push('\u{4}');
+                context.push_str(&id);
+
+                context
+            },
+            None => id,
+        };
+
+        if entry.id_plural.is_some() {
+            for (i, s) in entry.strs {
+                if !s.is_empty() {
+                    map.insert(format!("{}[{}]", key, i), s);
+                }
+            }
+        } else if let Some((_, s)) = entry.strs.into_iter().next() {
+            if !s.is_empty() {
+                map.insert(key, s);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Parse a quoted string which starts at `index` and return the index after it.
+fn parse_string(
+    chars: &[char],
+    index: usize,
+    line_number: usize,
+    output: &mut String,
+) -> Result<usize, ParseError> {
+    let mut i = index + 1;
+
+    loop {
+        match chars.get(i) {
+            Some('"') => return Ok(i + 1),
+            Some('\\') => {
+                let c = match chars.get(i + 1) {
+                    Some('n') => '\n',
+                    Some('t') => '\t',
+                    Some('r') => '\r',
+                    Some('a') => '\u{7}',
+                    Some('b') => '\u{8}',
+                    Some('f') => '\u{C}',
+                    Some('v') => '\u{B}',
+                    Some(c @ ('\\' | '"' | '\'' | '?')) => *c,
+                    _ => {
+                        return Err(ParseError::new(
+                            ParseErrorKind::InvalidEscape,
+                            line_number,
+                            i + 1,
+                        ))
+                    },
+                };
+
+                output.push(c);
+                i += 2;
+            },
+            Some(c) => {
+                output.push(*c);
+                i += 1;
+            },
+            None => {
+                return Err(ParseError::new(
+                    ParseErrorKind::UnterminatedString,
+                    line_number,
+                    index + 1,
+                ))
+            },
+        }
+    }
+}
+
+/// Parse the quoted string at `index`, which may be preceded by whitespace, and check that nothing follows it.
+fn parse_string_to_end(
+    chars: &[char],
+    mut index: usize,
+    line_number: usize,
+    output: &mut String,
+) -> Result<(), ParseError> {
+    while index < chars.len() && chars[index].is_whitespace() {
+        index += 1;
+    }
+
+    if chars.get(index) != Some(&'"') {
+        return Err(ParseError::new(ParseErrorKind::ExpectedString, line_number, index + 1));
+    }
+
+    index = parse_string(chars, index, line_number, output)?;
+
+    match chars[index..].iter().position(|c| !c.is_whitespace()) {
+        Some(i) => {
+            Err(ParseError::new(ParseErrorKind::TrailingCharacters, line_number, index + i + 1))
+        },
+        None => Ok(()),
+    }
+}
+
+/// Parse a gettext `.po` text into a map.
+///
+/// The header entry, fuzzy entries, obsolete entries and untranslated entries are skipped. The key of an entry with `msgctxt` is the context and the `msgid` joined by `\u{4}`, as gettext does. Each `msgstr[n]` of a plural entry is stored with the key suffixed with `[n]`.
+///
+/// ```
+/// use map_to_javascript_html::{parse_po, MapToJavaScriptHTML};
+///
+/// let map = parse_po(
+///     r#"
+/// msgid "hello"
+/// msgstr "Bonjour"
+///
+/// msgid "apple"
+/// msgid_plural "apples"
+/// msgstr[0] "pomme"
+/// msgstr[1] "pommes"
+/// "#,
+/// )
+/// .unwrap();
+///
+/// assert_eq!(
+///     "_text['hello']='Bonjour';_text['apple[1]']='pommes';",
+///     map.to_javascript_html_with_keys("_text", &["hello", "apple[1]"])
+/// );
+/// ```
+pub fn parse_po(text: &str) -> Result<BTreeMap<String, String>, ParseError> {
+    let mut map = BTreeMap::new();
+
+    let mut entry = Entry::default();
+    let mut field = None;
+
+    for (index, line) in split_lines(text).enumerate() {
+        let line_number = index + 1;
+
+        let chars: Vec<char> = line.chars().collect();
+
+        let start = match chars.iter().position(|c| !c.is_whitespace()) {
+            Some(start) => start,
+            None => {
+                if entry.has_msgstr {
+                    entry.finish(&mut map)?;
+                    field = None;
+                }
+
+                continue;
+            },
+        };
+
+        match chars[start] {
+            '#' => {
+                if chars.get(start + 1) == Some(&'~') {
+                    continue;
+                }
+
+                if entry.has_msgstr {
+                    entry.finish(&mut map)?;
+                    field = None;
+                }
+
+                if chars.get(start + 1) == Some(&',') {
+                    let flags: String = chars[start + 2..].iter().collect();
+
+                    if flags.split(',').any(|flag| flag.trim() == "fuzzy") {
+                        entry.fuzzy = true;
+                    }
+                }
+            },
+            '"' => match field {
+                Some(field) => {
+                    parse_string_to_end(&chars, start, line_number, entry.field_mut(field))?
+                },
+                None => {
+                    return Err(ParseError::new(
+                        ParseErrorKind::UnexpectedString,
+                        line_number,
+                        start + 1,
+                    ))
+                },
+            },
+            _ => {
+                let end = chars[start..]
+                    .iter()
+                    .position(|c| c.is_whitespace() || *c == '"')
+                    .map_or(chars.len(), |i| start + i);
+
+                let keyword: String = chars[start..end].iter().collect();
+
+                let unexpected_keyword =
+                    || ParseError::new(ParseErrorKind::UnexpectedKeyword, line_number, start + 1);
+
+                let next_field = match keyword.as_str() {
+                    "msgctxt" | "msgid" => {
+                        if entry.has_msgstr {
+                            entry.finish(&mut map)?;
+                        }
+
+                        if entry.id.is_some() || (keyword == "msgctxt" && entry.context.is_some()) {
+                            return Err(unexpected_keyword());
+                        }
+
+                        if !entry.is_started() {
+                            entry.line = line_number;
+                        }
+
+                        if keyword == "msgctxt" {
+                            Field::Context
+                        } else {
+                            Field::Id
+                        }
+                    },
+                    "msgid_plural" => {
+                        if entry.id.is_none() || entry.id_plural.is_some() || entry.has_msgstr {
+                            return Err(unexpected_keyword());
+                        }
+
+                        Field::IdPlural
+                    },
+                    "msgstr" => {
+                        if entry.id.is_none() {
+                            return Err(ParseError::new(
+                                ParseErrorKind::MissingMsgid,
+                                line_number,
+                                start + 1,
+                            ));
+                        }
+
+                        if entry.id_plural.is_some() || entry.has_msgstr {
+                            return Err(unexpected_keyword());
+                        }
+
+                        entry.has_msgstr = true;
+                        entry.strs.push((0, String::new()));
+
+                        Field::Str(0)
+                    },
+                    _ if keyword.starts_with("msgstr[") && keyword.ends_with(']') => {
+                        if entry.id.is_none() {
+                            return Err(ParseError::new(
+                                ParseErrorKind::MissingMsgid,
+                                line_number,
+                                start + 1,
+                            ));
+                        }
+
+                        if entry.id_plural.is_none() {
+                            return Err(unexpected_keyword());
+                        }
+
+                        let n = match keyword[7..keyword.len() - 1].parse::<usize>() {
+                            Ok(n) if entry.strs.iter().all(|(i, _)| *i != n) => n,
+                            _ => {
+                                return Err(ParseError::new(
+                                    ParseErrorKind::InvalidPluralIndex,
+                                    line_number,
+                                    start + 8,
+                                ))
+                            },
+                        };
+
+                        entry.has_msgstr = true;
+                        entry.strs.push((n, String::new()));
+
+                        Field::Str(entry.strs.len() - 1)
+                    },
+                    _ => return Err(unexpected_keyword()),
+                };
+
+                parse_string_to_end(&chars, end, line_number, entry.field_mut(next_field))?;
+
+                field = Some(next_field);
+            },
+        }
+    }
+
+    entry.finish(&mut map)?;
+
+    Ok(map)
+}
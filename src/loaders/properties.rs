@@ -0,0 +1,203 @@
+use alloc::{collections::BTreeMap, string::String, vec::Vec};
+
+use super::{split_lines, ParseError, ParseErrorKind};
+
+/// A character of a logical line with its position in the text.
+struct PositionedChar {
+    c:      char,
+    line:   usize,
+    column: usize,
+}
+
+#[inline]
+fn is_whitespace(c: char) -> bool {
+    matches!(c, ' ' | '\t' | '\u{C}')
+}
+
+/// Push the characters of a natural line, without its leading whitespace, to a logical line. Return whether the natural line is continued by the next one.
+fn push_natural_line(
+    line: &str,
+    line_number: usize,
+    logical_line: &mut Vec<PositionedChar>,
+) -> bool {
+    let continued = line.chars().rev().take_while(|c| *c == '\\').count() % 2 == 1;
+
+    let chars = line.chars().enumerate().skip_while(|(_, c)| is_whitespace(*c));
+
+    logical_line.extend(chars.map(|(i, c)| PositionedChar {
+        c,
+        line: line_number,
+        column: i + 1,
+    }));
+
+    if continued {
+        logical_line.pop();
+    }
+
+    continued
+}
+
+#[inline]
+fn read_hex4(chars: &[PositionedChar], index: usize) -> Option<u32> {
+    let digits = chars.get(index..index + 4)?;
+
+    digits.iter().try_fold(0, |n, d| d.c.to_digit(16).map(|d| (n << 4) | d))
+}
+
+/// Unescape the escape sequence at `index`, which is a backslash, and return the index after the sequence.
+fn unescape(
+    chars: &[PositionedChar],
+    index: usize,
+    output: &mut String,
+) -> Result<usize, ParseError> {
+    let backslash = &chars[index];
+
+    let c = match chars.get(index + 1) {
+        Some(c) => c.c,
+        None => return Ok(index + 1),
+    };
+
+    match c {
+        't' => output.push('\t'),
+        'n' => output.push('\n'),
+        'r' => output.push('\r'),
+        'f' => output.push('\u{C}'),
+        'u' => {
+            let error = || {
+                ParseError::new(
+                    ParseErrorKind::InvalidUnicodeEscape,
+                    backslash.line,
+                    backslash.column,
+                )
+            };
+
+            let high = read_hex4(chars, index + 2).ok_or_else(error)?;
+
+            let (code_point, next) = match high {
+                0xD800..=0xDBFF => {
+                    let low = match (chars.get(index + 6), chars.get(index + 7)) {
+                        (Some(b), Some(u)) if b.c == '\\' && u.c == 'u' => {
+                            read_hex4(chars, index + 8)
+                        },
+                        _ => None,
+                    };
+
+                    match low {
+                        Some(low @ 0xDC00..=0xDFFF) => {
+                            (0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00), index + 12)
+                        },
+                        _ => return Err(error()),
+                    }
+                },
+                _ => (high, index + 6),
+            };
+
+            output.push(char::from_u32(code_point).ok_or_else(error)?);
+
+            return Ok(next);
+        },
+        _ => output.push(c),
+    }
+
+    Ok(index + 2)
+}
+
+fn parse_logical_line(
+    chars: &[PositionedChar],
+    map: &mut BTreeMap<String, String>,
+) -> Result<(), ParseError> {
+    let length = chars.len();
+
+    let mut key = String::new();
+    let mut i = 0;
+
+    while i < length {
+        match chars[i].c {
+            '\\' => i = unescape(chars, i, &mut key)?,
+            '=' | ':' => break,
+            c if is_whitespace(c) => break,
+            c => {
+                key.push(c);
+                i += 1;
+            },
+        }
+    }
+
+    while i < length && is_whitespace(chars[i].c) {
+        i += 1;
+    }
+
+    if i < length && matches!(chars[i].c, '=' | ':') {
+        i += 1;
+
+        while i < length && is_whitespace(chars[i].c) {
+            i += 1;
+        }
+    }
+
+    let mut value = String::new();
+
+    while i < length {
+        match chars[i].c {
+            '\\' => i = unescape(chars, i, &mut value)?,
+            c => {
+                value.push(c);
+                i += 1;
+            },
+        }
+    }
+
+    map.insert(key, value);
+
+    Ok(())
+}
+
+/// Parse a Java `.properties` text into a map.
+///
+/// Comments (`#` and `!`), the `=`, `:` and whitespace separators, line continuations and the `\t`, `\n`, `\r`, `\f` and `\uXXXX` escapes (including surrogate pairs) are supported. If a key appears more than once, the last value is used.
+///
+/// ```
+/// use map_to_javascript_html::{parse_properties, MapToJavaScriptHTML};
+///
+/// let map = parse_properties(
+///     "hello = Hello \\\n    world!\nwelcome: \\u6B61\\u8FCE",
+/// )
+/// .unwrap();
+///
+/// assert_eq!(
+///     "_text['hello']='Hello world!';_text['welcome']='歡迎';",
+///     map.to_javascript_html("_text")
+/// );
+/// ```
+pub fn parse_properties(text: &str) -> Result<BTreeMap<String, String>, ParseError> {
+    let mut map = BTreeMap::new();
+
+    let mut lines = split_lines(text).enumerate();
+
+    let mut logical_line = Vec::new();
+
+    while let Some((index, line)) = lines.next() {
+        let trimmed = line.trim_start_matches(is_whitespace);
+
+        if trimmed.is_empty() || trimmed.starts_with(['#', '!']) {
+            continue;
+        }
+
+        logical_line.clear();
+
+        let mut continued = push_natural_line(line, index + 1, &mut logical_line);
+
+        while continued {
+            match lines.next() {
+                Some((index, line)) => {
+                    continued = push_natural_line(line, index + 1, &mut logical_line);
+                },
+                None => break,
+            }
+        }
+
+        parse_logical_line(&logical_line, &mut map)?;
+    }
+
+    Ok(map)
+}
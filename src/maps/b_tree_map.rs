@@ -1,9 +1,9 @@
-use alloc::{collections::BTreeMap, vec::Vec};
+use alloc::{borrow::Cow, boxed::Box, collections::BTreeMap, vec::Vec};
 use core::{borrow::Borrow, fmt::Display};
 #[cfg(feature = "std")]
 use std::io::{self, Write};
 
-use crate::MapToJavaScriptHTML;
+use crate::{escape, JavaScriptEntries, JsValue, MapToJavaScriptHTML};
 
 impl<K: Display + Ord, V: Display> MapToJavaScriptHTML<K> for BTreeMap<K, V> {
     fn to_javascript_html_to_vec<'a, S: Display>(
@@ -20,9 +20,9 @@ impl<K: Display + Ord, V: Display> MapToJavaScriptHTML<K> for BTreeMap<K, V> {
         for (key, value) in self {
             output.extend_from_slice(variable_name.as_bytes());
             output.extend_from_slice(b"['");
-            html_escape::encode_script_single_quoted_text_to_vec(format!("{}", key), output);
+            escape::encode_script_single_quoted_text_to_vec(format!("{}", key), output);
             output.extend_from_slice(b"']='");
-            html_escape::encode_script_single_quoted_text_to_vec(format!("{}", value), output);
+            escape::encode_script_single_quoted_text_to_vec(format!("{}", value), output);
             output.extend_from_slice(b"';");
         }
 
@@ -40,9 +40,9 @@ impl<K: Display + Ord, V: Display> MapToJavaScriptHTML<K> for BTreeMap<K, V> {
         for (key, value) in self {
             output.write_all(variable_name.as_bytes())?;
             output.write_all(b"['")?;
-            html_escape::encode_script_single_quoted_text_to_writer(format!("{}", key), output)?;
+            escape::encode_script_single_quoted_text_to_writer(format!("{}", key), output)?;
             output.write_all(b"']='")?;
-            html_escape::encode_script_single_quoted_text_to_writer(format!("{}", value), output)?;
+            escape::encode_script_single_quoted_text_to_writer(format!("{}", value), output)?;
             output.write_all(b"';")?;
         }
 
@@ -67,15 +67,12 @@ impl<K: Display + Ord, V: Display> MapToJavaScriptHTML<K> for BTreeMap<K, V> {
         for key in keys.iter() {
             output.extend_from_slice(variable_name.as_bytes());
             output.extend_from_slice(b"['");
-            html_escape::encode_script_single_quoted_text_to_vec(format!("{}", key), output);
+            escape::encode_script_single_quoted_text_to_vec(format!("{}", key), output);
             output.extend_from_slice(b"']=");
             match self.get(key) {
                 Some(value) => {
                     output.push(b'\'');
-                    html_escape::encode_script_single_quoted_text_to_vec(
-                        format!("{}", value),
-                        output,
-                    );
+                    escape::encode_script_single_quoted_text_to_vec(format!("{}", value), output);
                     output.extend_from_slice(b"';");
                 },
                 None => {
@@ -101,12 +98,12 @@ impl<K: Display + Ord, V: Display> MapToJavaScriptHTML<K> for BTreeMap<K, V> {
         for key in keys.iter() {
             output.write_all(variable_name.as_bytes())?;
             output.write_all(b"['")?;
-            html_escape::encode_script_single_quoted_text_to_writer(format!("{}", key), output)?;
+            escape::encode_script_single_quoted_text_to_writer(format!("{}", key), output)?;
             output.write_all(b"']=")?;
             match self.get(key) {
                 Some(value) => {
                     output.write_all(b"'")?;
-                    html_escape::encode_script_single_quoted_text_to_writer(
+                    escape::encode_script_single_quoted_text_to_writer(
                         format!("{}", value),
                         output,
                     )?;
@@ -121,3 +118,12 @@ impl<K: Display + Ord, V: Display> MapToJavaScriptHTML<K> for BTreeMap<K, V> {
         Ok(())
     }
 }
+
+impl<K: Display + Ord, V: Display> JavaScriptEntries for BTreeMap<K, V> {
+    #[inline]
+    fn javascript_entries(&self) -> Box<dyn Iterator<Item = (Cow<'_, str>, JsValue<'_>)> + '_> {
+        Box::new(self.iter().map(|(key, value)| {
+            (Cow::Owned(format!("{}", key)), JsValue::String(Cow::Owned(format!("{}", value))))
+        }))
+    }
+}
@@ -1,11 +1,11 @@
-use alloc::vec::Vec;
+use alloc::{borrow::Cow, boxed::Box, vec::Vec};
 use core::{borrow::Borrow, fmt::Display, hash::Hash};
 #[cfg(feature = "std")]
 use std::collections::HashMap;
 #[cfg(feature = "std")]
 use std::io::{self, Write};
 
-use crate::MapToJavaScriptHTML;
+use crate::{escape, JavaScriptEntries, JsValue, MapToJavaScriptHTML};
 
 impl<K: Display + Eq + Hash, V: Display> MapToJavaScriptHTML<K> for HashMap<K, V> {
     fn to_javascript_html_to_vec<'a, S: Display>(
@@ -22,9 +22,9 @@ impl<K: Display + Eq + Hash, V: Display> MapToJavaScriptHTML<K> for HashMap<K, V
         for (key, value) in self {
             output.extend_from_slice(variable_name.as_bytes());
             output.extend_from_slice(b"['");
-            html_escape::encode_script_single_quoted_text_to_vec(format!("{}", key), output);
+            escape::encode_script_single_quoted_text_to_vec(format!("{}", key), output);
             output.extend_from_slice(b"']='");
-            html_escape::encode_script_single_quoted_text_to_vec(format!("{}", value), output);
+            escape::encode_script_single_quoted_text_to_vec(format!("{}", value), output);
             output.extend_from_slice(b"';");
         }
 
@@ -42,9 +42,9 @@ impl<K: Display + Eq + Hash, V: Display> MapToJavaScriptHTML<K> for HashMap<K, V
         for (key, value) in self {
             output.write_all(variable_name.as_bytes())?;
             output.write_all(b"['")?;
-            html_escape::encode_script_single_quoted_text_to_writer(format!("{}", key), output)?;
+            escape::encode_script_single_quoted_text_to_writer(format!("{}", key), output)?;
             output.write_all(b"']='")?;
-            html_escape::encode_script_single_quoted_text_to_writer(format!("{}", value), output)?;
+            escape::encode_script_single_quoted_text_to_writer(format!("{}", value), output)?;
             output.write_all(b"';")?;
         }
 
@@ -69,15 +69,12 @@ impl<K: Display + Eq + Hash, V: Display> MapToJavaScriptHTML<K> for HashMap<K, V
         for key in keys.iter() {
             output.extend_from_slice(variable_name.as_bytes());
             output.extend_from_slice(b"['");
-            html_escape::encode_script_single_quoted_text_to_vec(format!("{}", key), output);
+            escape::encode_script_single_quoted_text_to_vec(format!("{}", key), output);
             output.extend_from_slice(b"']=");
             match self.get(key) {
                 Some(value) => {
                     output.push(b'\'');
-                    html_escape::encode_script_single_quoted_text_to_vec(
-                        format!("{}", value),
-                        output,
-                    );
+                    escape::encode_script_single_quoted_text_to_vec(format!("{}", value), output);
                     output.extend_from_slice(b"';");
                 },
                 None => {
@@ -107,12 +104,12 @@ impl<K: Display + Eq + Hash, V: Display> MapToJavaScriptHTML<K> for HashMap<K, V
         for key in keys.iter() {
             output.write_all(variable_name.as_bytes())?;
             output.write_all(b"['")?;
-            html_escape::encode_script_single_quoted_text_to_writer(format!("{}", key), output)?;
+            escape::encode_script_single_quoted_text_to_writer(format!("{}", key), output)?;
             output.write_all(b"']=")?;
             match self.get(key) {
                 Some(value) => {
                     output.write_all(b"'")?;
-                    html_escape::encode_script_single_quoted_text_to_writer(
+                    escape::encode_script_single_quoted_text_to_writer(
                         format!("{}", value),
                         output,
                     )?;
@@ -127,3 +124,12 @@ impl<K: Display + Eq + Hash, V: Display> MapToJavaScriptHTML<K> for HashMap<K, V
         Ok(())
     }
 }
+
+impl<K: Display + Eq + Hash, V: Display> JavaScriptEntries for HashMap<K, V> {
+    #[inline]
+    fn javascript_entries(&self) -> Box<dyn Iterator<Item = (Cow<'_, str>, JsValue<'_>)> + '_> {
+        Box::new(self.iter().map(|(key, value)| {
+            (Cow::Owned(format!("{}", key)), JsValue::String(Cow::Owned(format!("{}", value))))
+        }))
+    }
+}
@@ -1,3 +1,6 @@
+// the modules only have trait implementations for now, but their public items are re-exported
+#![allow(unused_imports)]
+
 mod b_tree_map;
 
 #[cfg(feature = "std")]
@@ -6,8 +9,18 @@ mod hash_map;
 #[cfg(feature = "serde_json")]
 mod serde_json_map;
 
+#[cfg(feature = "serde_yaml")]
+mod serde_yaml_mapping;
+
+#[cfg(feature = "toml")]
+mod toml_table;
+
 pub use b_tree_map::*;
 #[cfg(feature = "std")]
 pub use hash_map::*;
 #[cfg(feature = "serde_json")]
 pub use serde_json_map::*;
+#[cfg(feature = "serde_yaml")]
+pub use serde_yaml_mapping::*;
+#[cfg(feature = "toml")]
+pub use toml_table::*;
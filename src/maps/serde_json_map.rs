@@ -1,11 +1,26 @@
-use alloc::{string::String, vec::Vec};
+use alloc::{borrow::Cow, boxed::Box, string::String, vec::Vec};
 use core::{borrow::Borrow, fmt::Display, hash::Hash};
 #[cfg(feature = "std")]
 use std::io::{self, Write};
 
 use serde_json::{Map, Value};
 
-use crate::MapToJavaScriptHTML;
+use crate::{escape, JavaScriptEntries, JsNumber, JsValue, MapToJavaScriptHTML};
+
+fn value_to_js_value(value: &Value) -> JsValue<'_> {
+    match value {
+        Value::Null => JsValue::Null,
+        Value::Bool(b) => JsValue::Bool(*b),
+        Value::Number(n) => JsValue::Number(
+            JsNumber::parse(format!("{}", n)).unwrap_or_else(|| JsNumber::from(f64::NAN)),
+        ),
+        Value::String(s) => JsValue::String(Cow::Borrowed(s)),
+        Value::Array(array) => JsValue::Array(array.iter().map(value_to_js_value).collect()),
+        Value::Object(object) => JsValue::Object(
+            object.iter().map(|(k, v)| (Cow::Borrowed(k.as_str()), value_to_js_value(v))).collect(),
+        ),
+    }
+}
 
 #[inline]
 fn value_to_javascript_value_end_with_semicolon_in_html_to_vec(
@@ -16,7 +31,7 @@ fn value_to_javascript_value_end_with_semicolon_in_html_to_vec(
         Value::Null => output.extend_from_slice(b"null;"),
         Value::String(s) => {
             output.push(b'\'');
-            html_escape::encode_script_single_quoted_text_to_vec(s, output);
+            escape::encode_script_single_quoted_text_to_vec(s, output);
             output.extend_from_slice(b"\';");
         },
         Value::Bool(b) => {
@@ -28,7 +43,7 @@ fn value_to_javascript_value_end_with_semicolon_in_html_to_vec(
         Value::Object(_) | Value::Array(_) => {
             let json = format!("{}", value);
 
-            html_escape::encode_script_to_vec(json, output);
+            escape::encode_script_to_vec(json, output);
             output.push(b';');
         },
     }
@@ -44,7 +59,7 @@ fn value_to_javascript_value_end_with_semicolon_in_html_to_writer<W: Write>(
         Value::Null => output.write_all(b"null;"),
         Value::String(s) => {
             output.write_all(b"'")?;
-            html_escape::encode_script_single_quoted_text_to_writer(s, output)?;
+            escape::encode_script_single_quoted_text_to_writer(s, output)?;
             output.write_all(b"\';")
         },
         Value::Bool(b) => output.write_fmt(format_args!("{};", b)),
@@ -52,7 +67,7 @@ fn value_to_javascript_value_end_with_semicolon_in_html_to_writer<W: Write>(
         Value::Object(_) | Value::Array(_) => {
             let json = format!("{}", value);
 
-            html_escape::encode_script_to_writer(json, output)?;
+            escape::encode_script_to_writer(json, output)?;
             output.write_all(b";")
         },
     }
@@ -73,7 +88,7 @@ impl MapToJavaScriptHTML<String> for Map<String, Value> {
         for (key, value) in self {
             output.extend_from_slice(variable_name.as_bytes());
             output.extend_from_slice(b"['");
-            html_escape::encode_script_single_quoted_text_to_vec(key, output);
+            escape::encode_script_single_quoted_text_to_vec(key, output);
             output.extend_from_slice(b"']=");
             value_to_javascript_value_end_with_semicolon_in_html_to_vec(value, output);
         }
@@ -92,7 +107,7 @@ impl MapToJavaScriptHTML<String> for Map<String, Value> {
         for (key, value) in self {
             output.write_all(variable_name.as_bytes())?;
             output.write_all(b"['")?;
-            html_escape::encode_script_single_quoted_text_to_writer(key, output)?;
+            escape::encode_script_single_quoted_text_to_writer(key, output)?;
             output.write_all(b"']=")?;
             value_to_javascript_value_end_with_semicolon_in_html_to_writer(value, output)?;
         }
@@ -118,7 +133,7 @@ impl MapToJavaScriptHTML<String> for Map<String, Value> {
         for key in keys.iter() {
             output.extend_from_slice(variable_name.as_bytes());
             output.extend_from_slice(b"['");
-            html_escape::encode_script_single_quoted_text_to_vec(format!("{}", key), output);
+            escape::encode_script_single_quoted_text_to_vec(format!("{}", key), output);
             output.extend_from_slice(b"']=");
             match self.get(key) {
                 Some(value) => {
@@ -151,7 +166,7 @@ impl MapToJavaScriptHTML<String> for Map<String, Value> {
         for key in keys.iter() {
             output.write_all(variable_name.as_bytes())?;
             output.write_all(b"['")?;
-            html_escape::encode_script_single_quoted_text_to_writer(format!("{}", key), output)?;
+            escape::encode_script_single_quoted_text_to_writer(format!("{}", key), output)?;
             output.write_all(b"']=")?;
             match self.get(key) {
                 Some(value) => {
@@ -166,3 +181,17 @@ impl MapToJavaScriptHTML<String> for Map<String, Value> {
         Ok(())
     }
 }
+
+impl JavaScriptEntries for Map<String, Value> {
+    #[inline]
+    fn javascript_entries(&self) -> Box<dyn Iterator<Item = (Cow<'_, str>, JsValue<'_>)> + '_> {
+        Box::new(
+            self.iter().map(|(key, value)| (Cow::Borrowed(key.as_str()), value_to_js_value(value))),
+        )
+    }
+
+    #[inline]
+    fn javascript_entry(&self, key: &str) -> Option<JsValue<'_>> {
+        self.get(key).map(value_to_js_value)
+    }
+}
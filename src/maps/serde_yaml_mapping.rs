@@ -0,0 +1,316 @@
+use alloc::{borrow::Cow, boxed::Box, string::String, vec::Vec};
+use core::{borrow::Borrow, fmt::Display, hash::Hash};
+#[cfg(feature = "std")]
+use std::io::{self, Write};
+
+use serde_yaml::{Mapping, Number, Value};
+
+use crate::{
+    escape,
+    json_text::{push_f64, push_json_string},
+    JavaScriptEntries, JsNumber, JsValue, MapToJavaScriptHTML,
+};
+
+fn number_to_json_text(n: &Number, output: &mut String) {
+    if n.is_f64() {
+        push_f64(n.as_f64().unwrap(), output);
+    } else {
+        output.push_str(&format!("{}", n));
+    }
+}
+
+/// Convert a YAML key to a property name. Sequences and mappings become JSON texts rather than their JavaScript strings.
+fn key_to_string(key: &Value) -> Cow<'_, str> {
+    match key {
+        Value::String(s) => Cow::Borrowed(s),
+        Value::Null => Cow::Borrowed("null"),
+        Value::Bool(b) => Cow::Borrowed(if *b { "true" } else { "false" }),
+        Value::Number(n) => {
+            let mut s = String::new();
+
+            number_to_json_text(n, &mut s);
+
+            Cow::Owned(s)
+        },
+        Value::Sequence(_) | Value::Mapping(_) => {
+            let mut s = String::new();
+
+            value_to_json_text(key, &mut s);
+
+            Cow::Owned(s)
+        },
+        Value::Tagged(tagged) => key_to_string(&tagged.value),
+    }
+}
+
+fn value_to_json_text(value: &Value, output: &mut String) {
+    match value {
+        Value::Null => output.push_str("null"),
+        Value::Bool(b) => output.push_str(if *b { "true" } else { "false" }),
+        Value::Number(n) => number_to_json_text(n, output),
+        Value::String(s) => push_json_string(s, output),
+        Value::Sequence(sequence) => {
+            output.push('[');
+
+            for (i, value) in sequence.iter().enumerate() {
+                if i > 0 {
+                    output.push(',');
+                }
+
+                value_to_json_text(value, output);
+            }
+
+            output.push(']');
+        },
+        Value::Mapping(mapping) => {
+            output.push('{');
+
+            for (i, (key, value)) in mapping.iter().enumerate() {
+                if i > 0 {
+                    output.push(',');
+                }
+
+                push_json_string(&key_to_string(key), output);
+                output.push(':');
+                value_to_json_text(value, output);
+            }
+
+            output.push('}');
+        },
+        Value::Tagged(tagged) => value_to_json_text(&tagged.value, output),
+    }
+}
+
+fn number_to_js_number(n: &Number) -> JsNumber {
+    if n.is_f64() {
+        JsNumber::from(n.as_f64().unwrap())
+    } else if let Some(i) = n.as_i64() {
+        JsNumber::from(i)
+    } else {
+        JsNumber::from(n.as_u64().unwrap())
+    }
+}
+
+fn value_to_js_value(value: &Value) -> JsValue<'_> {
+    match value {
+        Value::Null => JsValue::Null,
+        Value::Bool(b) => JsValue::Bool(*b),
+        Value::Number(n) => JsValue::Number(number_to_js_number(n)),
+        Value::String(s) => JsValue::String(Cow::Borrowed(s)),
+        Value::Sequence(sequence) => {
+            JsValue::Array(sequence.iter().map(value_to_js_value).collect())
+        },
+        Value::Mapping(mapping) => JsValue::Object(
+            mapping.iter().map(|(k, v)| (key_to_string(k), value_to_js_value(v))).collect(),
+        ),
+        Value::Tagged(tagged) => value_to_js_value(&tagged.value),
+    }
+}
+
+#[inline]
+fn find_value<'a>(mapping: &'a Mapping, key: &str) -> Option<&'a Value> {
+    mapping.get(key).or_else(|| {
+        mapping.iter().find(|(k, _)| !k.is_string() && key_to_string(k) == key).map(|(_, v)| v)
+    })
+}
+
+#[inline]
+fn value_to_javascript_value_end_with_semicolon_in_html_to_vec(
+    value: &Value,
+    output: &mut Vec<u8>,
+) {
+    match value {
+        Value::Null => output.extend_from_slice(b"null;"),
+        Value::String(s) => {
+            output.push(b'\'');
+            escape::encode_script_single_quoted_text_to_vec(s, output);
+            output.extend_from_slice(b"\';");
+        },
+        Value::Bool(b) => {
+            output.extend_from_slice(format!("{};", b).as_bytes());
+        },
+        Value::Number(n) => {
+            let mut s = String::new();
+
+            number_to_json_text(n, &mut s);
+
+            output.extend_from_slice(s.as_bytes());
+            output.push(b';');
+        },
+        Value::Sequence(_) | Value::Mapping(_) => {
+            let mut json = String::new();
+
+            value_to_json_text(value, &mut json);
+
+            escape::encode_script_to_vec(json, output);
+            output.push(b';');
+        },
+        Value::Tagged(tagged) => {
+            value_to_javascript_value_end_with_semicolon_in_html_to_vec(&tagged.value, output)
+        },
+    }
+}
+
+#[cfg(feature = "std")]
+#[inline]
+fn value_to_javascript_value_end_with_semicolon_in_html_to_writer<W: Write>(
+    value: &Value,
+    output: &mut W,
+) -> Result<(), io::Error> {
+    match value {
+        Value::Null => output.write_all(b"null;"),
+        Value::String(s) => {
+            output.write_all(b"'")?;
+            escape::encode_script_single_quoted_text_to_writer(s, output)?;
+            output.write_all(b"\';")
+        },
+        Value::Bool(b) => output.write_fmt(format_args!("{};", b)),
+        Value::Number(n) => {
+            let mut s = String::new();
+
+            number_to_json_text(n, &mut s);
+
+            output.write_all(s.as_bytes())?;
+            output.write_all(b";")
+        },
+        Value::Sequence(_) | Value::Mapping(_) => {
+            let mut json = String::new();
+
+            value_to_json_text(value, &mut json);
+
+            escape::encode_script_to_writer(json, output)?;
+            output.write_all(b";")
+        },
+        Value::Tagged(tagged) => {
+            value_to_javascript_value_end_with_semicolon_in_html_to_writer(&tagged.value, output)
+        },
+    }
+}
+
+/// Keys which are not strings are converted to property names. `null`, booleans and numbers are written in the forms JavaScript would use for them (`.nan` and `.inf` become `NaN` and `Infinity`). Sequences and mappings are written as compact JSON texts, unlike JavaScript, which would convert them to strings such as `a,b` and `[object Object]` and make different keys collide. Tags are ignored.
+///
+/// The keys used with the `to_javascript_html_with_keys*` methods are matched against the converted property names.
+impl MapToJavaScriptHTML<String> for Mapping {
+    fn to_javascript_html_to_vec<'a, S: Display>(
+        &self,
+        variable_name: S,
+        output: &'a mut Vec<u8>,
+    ) -> &'a [u8] {
+        let variable_name = format!("{}", variable_name);
+
+        let current_length = output.len();
+
+        output.reserve((variable_name.len() + 11) * self.len());
+
+        for (key, value) in self {
+            output.extend_from_slice(variable_name.as_bytes());
+            output.extend_from_slice(b"['");
+            escape::encode_script_single_quoted_text_to_vec(key_to_string(key), output);
+            output.extend_from_slice(b"']=");
+            value_to_javascript_value_end_with_semicolon_in_html_to_vec(value, output);
+        }
+
+        &output[current_length..]
+    }
+
+    #[cfg(feature = "std")]
+    fn to_javascript_html_to_writer<S: Display, W: Write>(
+        &self,
+        variable_name: S,
+        output: &mut W,
+    ) -> Result<(), io::Error> {
+        let variable_name = format!("{}", variable_name);
+
+        for (key, value) in self {
+            output.write_all(variable_name.as_bytes())?;
+            output.write_all(b"['")?;
+            escape::encode_script_single_quoted_text_to_writer(key_to_string(key), output)?;
+            output.write_all(b"']=")?;
+            value_to_javascript_value_end_with_semicolon_in_html_to_writer(value, output)?;
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    fn to_javascript_html_with_keys_to_vec<'a, S: Display, KS: ?Sized + Display + Ord + Hash>(
+        &self,
+        variable_name: S,
+        keys: &[&KS],
+        output: &'a mut Vec<u8>,
+    ) -> &'a [u8]
+    where
+        String: Borrow<KS>, {
+        let variable_name = format!("{}", variable_name);
+
+        let current_length = output.len();
+
+        output.reserve((variable_name.len() + 11) * self.len());
+
+        for key in keys.iter() {
+            let key = format!("{}", key);
+
+            output.extend_from_slice(variable_name.as_bytes());
+            output.extend_from_slice(b"['");
+            escape::encode_script_single_quoted_text_to_vec(&key, output);
+            output.extend_from_slice(b"']=");
+            match find_value(self, &key) {
+                Some(value) => {
+                    value_to_javascript_value_end_with_semicolon_in_html_to_vec(value, output);
+                },
+                None => {
+                    output.extend_from_slice(b"undefined;");
+                },
+            }
+        }
+
+        &output[current_length..]
+    }
+
+    #[cfg(feature = "std")]
+    fn to_javascript_html_with_keys_to_writer<
+        S: Display,
+        W: Write,
+        KS: ?Sized + Display + Ord + Hash,
+    >(
+        &self,
+        variable_name: S,
+        keys: &[&KS],
+        output: &mut W,
+    ) -> Result<(), io::Error>
+    where
+        String: Borrow<KS>, {
+        let variable_name = format!("{}", variable_name);
+
+        for key in keys.iter() {
+            let key = format!("{}", key);
+
+            output.write_all(variable_name.as_bytes())?;
+            output.write_all(b"['")?;
+            escape::encode_script_single_quoted_text_to_writer(&key, output)?;
+            output.write_all(b"']=")?;
+            match find_value(self, &key) {
+                Some(value) => {
+                    value_to_javascript_value_end_with_semicolon_in_html_to_writer(value, output)?;
+                },
+                None => {
+                    output.write_all(b"undefined;")?;
+                },
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl JavaScriptEntries for Mapping {
+    #[inline]
+    fn javascript_entries(&self) -> Box<dyn Iterator<Item = (Cow<'_, str>, JsValue<'_>)> + '_> {
+        Box::new(self.iter().map(|(key, value)| (key_to_string(key), value_to_js_value(value))))
+    }
+
+    #[inline]
+    fn javascript_entry(&self, key: &str) -> Option<JsValue<'_>> {
+        find_value(self, key).map(value_to_js_value)
+    }
+}
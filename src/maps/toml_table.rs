@@ -0,0 +1,267 @@
+use alloc::{borrow::Cow, boxed::Box, string::String, vec::Vec};
+use core::{borrow::Borrow, fmt::Display, hash::Hash};
+#[cfg(feature = "std")]
+use std::io::{self, Write};
+
+use toml::{Table, Value};
+
+use crate::{
+    escape,
+    json_text::{push_f64, push_json_string},
+    JavaScriptEntries, JsNumber, JsValue, MapToJavaScriptHTML,
+};
+
+fn value_to_js_value(value: &Value) -> JsValue<'_> {
+    match value {
+        Value::String(s) => JsValue::String(Cow::Borrowed(s)),
+        Value::Integer(i) => JsValue::Number(JsNumber::from(*i)),
+        Value::Float(f) => JsValue::Number(JsNumber::from(*f)),
+        Value::Boolean(b) => JsValue::Bool(*b),
+        Value::Datetime(d) => JsValue::String(Cow::Owned(format!("{}", d))),
+        Value::Array(array) => JsValue::Array(array.iter().map(value_to_js_value).collect()),
+        Value::Table(table) => JsValue::Object(
+            table.iter().map(|(k, v)| (Cow::Borrowed(k.as_str()), value_to_js_value(v))).collect(),
+        ),
+    }
+}
+
+fn value_to_json_text(value: &Value, output: &mut String) {
+    match value {
+        Value::String(s) => push_json_string(s, output),
+        Value::Integer(i) => output.push_str(&format!("{}", i)),
+        Value::Float(f) => push_f64(*f, output),
+        Value::Boolean(b) => output.push_str(if *b { "true" } else { "false" }),
+        Value::Datetime(d) => push_json_string(&format!("{}", d), output),
+        Value::Array(array) => {
+            output.push('[');
+
+            for (i, value) in array.iter().enumerate() {
+                if i > 0 {
+                    output.push(',');
+                }
+
+                value_to_json_text(value, output);
+            }
+
+            output.push(']');
+        },
+        Value::Table(table) => {
+            output.push('{');
+
+            for (i, (key, value)) in table.iter().enumerate() {
+                if i > 0 {
+                    output.push(',');
+                }
+
+                push_json_string(key, output);
+                output.push(':');
+                value_to_json_text(value, output);
+            }
+
+            output.push('}');
+        },
+    }
+}
+
+#[inline]
+fn value_to_javascript_value_end_with_semicolon_in_html_to_vec(
+    value: &Value,
+    output: &mut Vec<u8>,
+) {
+    match value {
+        Value::String(s) => {
+            output.push(b'\'');
+            escape::encode_script_single_quoted_text_to_vec(s, output);
+            output.extend_from_slice(b"\';");
+        },
+        Value::Integer(i) => {
+            output.extend_from_slice(format!("{};", i).as_bytes());
+        },
+        Value::Float(f) => {
+            let mut s = String::new();
+
+            push_f64(*f, &mut s);
+
+            output.extend_from_slice(s.as_bytes());
+            output.push(b';');
+        },
+        Value::Boolean(b) => {
+            output.extend_from_slice(format!("{};", b).as_bytes());
+        },
+        Value::Datetime(d) => {
+            output.push(b'\'');
+            escape::encode_script_single_quoted_text_to_vec(format!("{}", d), output);
+            output.extend_from_slice(b"\';");
+        },
+        Value::Array(_) | Value::Table(_) => {
+            let mut json = String::new();
+
+            value_to_json_text(value, &mut json);
+
+            escape::encode_script_to_vec(json, output);
+            output.push(b';');
+        },
+    }
+}
+
+#[cfg(feature = "std")]
+#[inline]
+fn value_to_javascript_value_end_with_semicolon_in_html_to_writer<W: Write>(
+    value: &Value,
+    output: &mut W,
+) -> Result<(), io::Error> {
+    match value {
+        Value::String(s) => {
+            output.write_all(b"'")?;
+            escape::encode_script_single_quoted_text_to_writer(s, output)?;
+            output.write_all(b"\';")
+        },
+        Value::Integer(i) => output.write_fmt(format_args!("{};", i)),
+        Value::Float(f) => {
+            let mut s = String::new();
+
+            push_f64(*f, &mut s);
+
+            output.write_all(s.as_bytes())?;
+            output.write_all(b";")
+        },
+        Value::Boolean(b) => output.write_fmt(format_args!("{};", b)),
+        Value::Datetime(d) => {
+            output.write_all(b"'")?;
+            escape::encode_script_single_quoted_text_to_writer(format!("{}", d), output)?;
+            output.write_all(b"\';")
+        },
+        Value::Array(_) | Value::Table(_) => {
+            let mut json = String::new();
+
+            value_to_json_text(value, &mut json);
+
+            escape::encode_script_to_writer(json, output)?;
+            output.write_all(b";")
+        },
+    }
+}
+
+/// TOML datetimes are written as strings in the RFC 3339 format. Floats which are not finite are written as `NaN`, `Infinity` or `-Infinity`.
+impl MapToJavaScriptHTML<String> for Table {
+    fn to_javascript_html_to_vec<'a, S: Display>(
+        &self,
+        variable_name: S,
+        output: &'a mut Vec<u8>,
+    ) -> &'a [u8] {
+        let variable_name = format!("{}", variable_name);
+
+        let current_length = output.len();
+
+        output.reserve((variable_name.len() + 11) * self.len());
+
+        for (key, value) in self {
+            output.extend_from_slice(variable_name.as_bytes());
+            output.extend_from_slice(b"['");
+            escape::encode_script_single_quoted_text_to_vec(key, output);
+            output.extend_from_slice(b"']=");
+            value_to_javascript_value_end_with_semicolon_in_html_to_vec(value, output);
+        }
+
+        &output[current_length..]
+    }
+
+    #[cfg(feature = "std")]
+    fn to_javascript_html_to_writer<S: Display, W: Write>(
+        &self,
+        variable_name: S,
+        output: &mut W,
+    ) -> Result<(), io::Error> {
+        let variable_name = format!("{}", variable_name);
+
+        for (key, value) in self {
+            output.write_all(variable_name.as_bytes())?;
+            output.write_all(b"['")?;
+            escape::encode_script_single_quoted_text_to_writer(key, output)?;
+            output.write_all(b"']=")?;
+            value_to_javascript_value_end_with_semicolon_in_html_to_writer(value, output)?;
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    fn to_javascript_html_with_keys_to_vec<'a, S: Display, KS: ?Sized + Display + Ord + Hash>(
+        &self,
+        variable_name: S,
+        keys: &[&KS],
+        output: &'a mut Vec<u8>,
+    ) -> &'a [u8]
+    where
+        String: Borrow<KS>, {
+        let variable_name = format!("{}", variable_name);
+
+        let current_length = output.len();
+
+        output.reserve((variable_name.len() + 11) * self.len());
+
+        for key in keys.iter() {
+            output.extend_from_slice(variable_name.as_bytes());
+            output.extend_from_slice(b"['");
+            escape::encode_script_single_quoted_text_to_vec(format!("{}", key), output);
+            output.extend_from_slice(b"']=");
+            match self.get(key) {
+                Some(value) => {
+                    value_to_javascript_value_end_with_semicolon_in_html_to_vec(value, output);
+                },
+                None => {
+                    output.extend_from_slice(b"undefined;");
+                },
+            }
+        }
+
+        &output[current_length..]
+    }
+
+    #[cfg(feature = "std")]
+    fn to_javascript_html_with_keys_to_writer<
+        S: Display,
+        W: Write,
+        KS: ?Sized + Display + Ord + Hash,
+    >(
+        &self,
+        variable_name: S,
+        keys: &[&KS],
+        output: &mut W,
+    ) -> Result<(), io::Error>
+    where
+        String: Borrow<KS>, {
+        let variable_name = format!("{}", variable_name);
+
+        for key in keys.iter() {
+            output.write_all(variable_name.as_bytes())?;
+            output.write_all(b"['")?;
+            escape::encode_script_single_quoted_text_to_writer(format!("{}", key), output)?;
+            output.write_all(b"']=")?;
+            match self.get(key) {
+                Some(value) => {
+                    value_to_javascript_value_end_with_semicolon_in_html_to_writer(value, output)?;
+                },
+                None => {
+                    output.write_all(b"undefined;")?;
+                },
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl JavaScriptEntries for Table {
+    #[inline]
+    fn javascript_entries(&self) -> Box<dyn Iterator<Item = (Cow<'_, str>, JsValue<'_>)> + '_> {
+        Box::new(
+            self.iter().map(|(key, value)| (Cow::Borrowed(key.as_str()), value_to_js_value(value))),
+        )
+    }
+
+    #[inline]
+    fn javascript_entry(&self, key: &str) -> Option<JsValue<'_>> {
+        self.get(key).map(value_to_js_value)
+    }
+}
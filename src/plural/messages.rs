@@ -0,0 +1,227 @@
+use alloc::{
+    borrow::Cow,
+    boxed::Box,
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::{borrow::Borrow, fmt::Display, hash::Hash};
+#[cfg(feature = "std")]
+use std::io::{self, Write};
+
+use super::{PluralCategory, PluralRules};
+use crate::{
+    renderer::{write_entry_to_vec, write_value_to_vec},
+    JavaScriptEntries, JsFunction, JsValue, MapToJavaScriptHTML,
+};
+
+/// Messages which have variants for plural categories, such as `{one: "1 item", other: "# items"}`.
+///
+/// Each message is written as a function which takes a number, selects the variant by the embedded plural rules of the locale, and replaces every `#` in the variant with the number. The variant of `other` is used if the selected category has no variant. The variants are written like the values of maps, so a backslash in them starts an escape sequence.
+///
+/// ```
+/// use map_to_javascript_html::{
+///     MapToJavaScriptHTML, PluralCategory, PluralMessages,
+/// };
+///
+/// let mut messages = PluralMessages::new("en");
+///
+/// messages.insert("items", [
+///     (PluralCategory::One, "1 item"),
+///     (PluralCategory::Other, "# items"),
+/// ]);
+///
+/// assert_eq!(
+///     "_text['items']=function(n){var v={one:'1 item',other:'# \
+///      items'};return (v[(function(n){n=Math.abs(n);var \
+///      s=String(n).split('.')[1]||'',i=Math.floor(n),v=s.length;return \
+///      i===1&&v===0?'one':'other';})(n)]||v.other||'').split('#').join(n);};",
+///     messages.to_javascript_html("_text")
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct PluralMessages {
+    rules:    PluralRules,
+    selector: String,
+    messages: BTreeMap<String, JsValue<'static>>,
+}
+
+impl PluralMessages {
+    /// Create an empty set of messages for a locale, such as `en` or `pt-PT`.
+    #[inline]
+    pub fn new<L: AsRef<str>>(locale: L) -> PluralMessages {
+        let rules = PluralRules::for_locale(locale);
+        let selector = rules.to_javascript();
+
+        PluralMessages {
+            rules,
+            selector,
+            messages: BTreeMap::new(),
+        }
+    }
+
+    /// The plural rules of the locale.
+    #[inline]
+    pub fn rules(&self) -> &PluralRules {
+        &self.rules
+    }
+
+    /// Insert a message with its variants. If the key already exists, the message is replaced.
+    pub fn insert<K: Display, V: Display, I: IntoIterator<Item = (PluralCategory, V)>>(
+        &mut self,
+        key: K,
+        variants: I,
+    ) {
+        let variants: BTreeMap<PluralCategory, String> = variants
+            .into_iter()
+            .map(|(category, variant)| (category, variant.to_string()))
+            .collect();
+
+        let mut code = Vec::new();
+
+        code.extend_from_slice(b"function(n){var v={");
+
+        for (i, (category, variant)) in variants.iter().enumerate() {
+            if i > 0 {
+                code.push(b',');
+            }
+
+            code.extend_from_slice(category.as_str().as_bytes());
+            code.push(b':');
+            write_value_to_vec(&JsValue::String(Cow::Borrowed(variant)), &mut code);
+        }
+
+        code.extend_from_slice(b"};return (v[(");
+        code.extend_from_slice(self.selector.as_bytes());
+        code.extend_from_slice(b")(n)]||v.other||'').split('#').join(n);}");
+
+        // the code only contains valid UTF-8 strings and ASCII characters
+        let function = JsFunction {
+            code: unsafe { String::from_utf8_unchecked(code) }
+        };
+
+        self.messages.insert(key.to_string(), JsValue::Function(function));
+    }
+
+    /// The number of the messages.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.messages.len()
+    }
+
+    /// Whether there is no message.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.messages.is_empty()
+    }
+
+    /// Get the JavaScript function of a message.
+    #[inline]
+    pub fn get<K: AsRef<str>>(&self, key: K) -> Option<&str> {
+        match self.messages.get(key.as_ref()) {
+            Some(JsValue::Function(function)) => Some(function.as_str()),
+            _ => None,
+        }
+    }
+}
+
+impl MapToJavaScriptHTML<String> for PluralMessages {
+    fn to_javascript_html_to_vec<'a, S: Display>(
+        &self,
+        variable_name: S,
+        output: &'a mut Vec<u8>,
+    ) -> &'a [u8] {
+        let variable_name = format!("{}", variable_name);
+
+        let current_length = output.len();
+
+        for (key, value) in self.messages.iter() {
+            write_entry_to_vec(&variable_name, key, value, output);
+        }
+
+        &output[current_length..]
+    }
+
+    #[cfg(feature = "std")]
+    fn to_javascript_html_to_writer<S: Display, W: Write>(
+        &self,
+        variable_name: S,
+        output: &mut W,
+    ) -> Result<(), io::Error> {
+        let variable_name = format!("{}", variable_name);
+
+        let mut buffer = Vec::new();
+
+        for (key, value) in self.messages.iter() {
+            buffer.clear();
+            write_entry_to_vec(&variable_name, key, value, &mut buffer);
+            output.write_all(&buffer)?;
+        }
+
+        Ok(())
+    }
+
+    #[inline]
+    fn to_javascript_html_with_keys_to_vec<'a, S: Display, KS: ?Sized + Display + Ord + Hash>(
+        &self,
+        variable_name: S,
+        keys: &[&KS],
+        output: &'a mut Vec<u8>,
+    ) -> &'a [u8]
+    where
+        String: Borrow<KS>, {
+        let variable_name = format!("{}", variable_name);
+
+        let current_length = output.len();
+
+        for key in keys.iter() {
+            let value = self.messages.get(key).unwrap_or(&JsValue::Undefined);
+
+            write_entry_to_vec(&variable_name, &format!("{}", key), value, output);
+        }
+
+        &output[current_length..]
+    }
+
+    #[cfg(feature = "std")]
+    fn to_javascript_html_with_keys_to_writer<
+        S: Display,
+        W: Write,
+        KS: ?Sized + Display + Ord + Hash,
+    >(
+        &self,
+        variable_name: S,
+        keys: &[&KS],
+        output: &mut W,
+    ) -> Result<(), io::Error>
+    where
+        String: Borrow<KS>, {
+        let variable_name = format!("{}", variable_name);
+
+        let mut buffer = Vec::new();
+
+        for key in keys.iter() {
+            let value = self.messages.get(key).unwrap_or(&JsValue::Undefined);
+
+            buffer.clear();
+            write_entry_to_vec(&variable_name, &format!("{}", key), value, &mut buffer);
+            output.write_all(&buffer)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl JavaScriptEntries for PluralMessages {
+    #[inline]
+    fn javascript_entries(&self) -> Box<dyn Iterator<Item = (Cow<'_, str>, JsValue<'_>)> + '_> {
+        Box::new(
+            self.messages.iter().map(|(key, value)| (Cow::Borrowed(key.as_str()), value.clone())),
+        )
+    }
+
+    #[inline]
+    fn javascript_entry(&self, key: &str) -> Option<JsValue<'_>> {
+        self.messages.get(key).cloned()
+    }
+}
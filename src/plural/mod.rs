@@ -0,0 +1,358 @@
+mod data;
+mod messages;
+
+use alloc::{string::String, vec::Vec};
+use core::fmt::{self, Display, Formatter, Write};
+
+use data::PLURAL_RULES;
+pub use messages::*;
+
+/// A plural category of CLDR.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PluralCategory {
+    Zero,
+    One,
+    Two,
+    Few,
+    Many,
+    Other,
+}
+
+impl PluralCategory {
+    /// The name of this category, such as `"one"`.
+    #[inline]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            PluralCategory::Zero => "zero",
+            PluralCategory::One => "one",
+            PluralCategory::Two => "two",
+            PluralCategory::Few => "few",
+            PluralCategory::Many => "many",
+            PluralCategory::Other => "other",
+        }
+    }
+
+    /// Get a category by its name, such as `"one"`.
+    #[inline]
+    pub fn from_name<S: AsRef<str>>(name: S) -> Option<PluralCategory> {
+        match name.as_ref() {
+            "zero" => Some(PluralCategory::Zero),
+            "one" => Some(PluralCategory::One),
+            "two" => Some(PluralCategory::Two),
+            "few" => Some(PluralCategory::Few),
+            "many" => Some(PluralCategory::Many),
+            "other" => Some(PluralCategory::Other),
+            _ => None,
+        }
+    }
+}
+
+impl Display for PluralCategory {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A relation of a plural rule, such as `i % 10 = 2..4`.
+#[derive(Debug, Clone)]
+struct Relation {
+    operand: char,
+    modulus: Option<u64>,
+    negated: bool,
+    ranges:  Vec<(u64, u64)>,
+}
+
+/// The operands of a number, which are described in the section "Plural Operand Meanings" of UTS #35.
+struct Operands {
+    n: f64,
+    i: u64,
+    v: u64,
+    w: u64,
+    f: u64,
+    t: u64,
+}
+
+impl Operands {
+    fn new(n: f64) -> Operands {
+        // `f64::abs` is not in `core`
+        let n = if n < 0.0 { -n } else { n };
+
+        // the same as `String(n)` in JavaScript for the numbers which are not too large or too small
+        let s = format!("{}", n);
+
+        let fraction = s.split_once('.').map_or("", |(_, fraction)| fraction);
+        let trimmed_fraction = fraction.trim_end_matches('0');
+
+        Operands {
+            n,
+            i: n as u64,
+            v: fraction.len() as u64,
+            w: trimmed_fraction.len() as u64,
+            f: fraction.parse().unwrap_or(0),
+            t: trimmed_fraction.parse().unwrap_or(0),
+        }
+    }
+
+    fn get(&self, operand: char) -> f64 {
+        match operand {
+            'n' => self.n,
+            'i' => self.i as f64,
+            'v' => self.v as f64,
+            'w' => self.w as f64,
+            'f' => self.f as f64,
+            't' => self.t as f64,
+            _ => 0.0,
+        }
+    }
+}
+
+impl Relation {
+    fn matches(&self, operands: &Operands) -> bool {
+        let mut value = operands.get(self.operand);
+
+        if let Some(modulus) = self.modulus {
+            value %= modulus as f64;
+        }
+
+        let in_ranges = value % 1.0 == 0.0
+            && self
+                .ranges
+                .iter()
+                .any(|(start, end)| value >= *start as f64 && value <= *end as f64);
+
+        in_ranges != self.negated
+    }
+
+    fn write_javascript(&self, output: &mut String) {
+        let mut value = String::new();
+
+        value.push(self.operand);
+
+        if let Some(modulus) = self.modulus {
+            write!(value, "%{}", modulus).unwrap();
+        }
+
+        // a single value or range doesn't need parentheses in a conjunction
+        if let [(start, end)] = self.ranges.as_slice() {
+            if start == end {
+                let operator = if self.negated { "!==" } else { "===" };
+
+                write!(output, "{}{}{}", value, operator, start).unwrap();
+
+                return;
+            }
+
+            if !self.negated {
+                write_range(&value, self.operand, *start, *end, output);
+
+                return;
+            }
+        }
+
+        if self.negated {
+            output.push_str("!(");
+        } else {
+            output.push('(');
+        }
+
+        for (i, (start, end)) in self.ranges.iter().enumerate() {
+            if i > 0 {
+                output.push_str("||");
+            }
+
+            if start == end {
+                write!(output, "{}==={}", value, start).unwrap();
+            } else {
+                write_range(&value, self.operand, *start, *end, output);
+            }
+        }
+
+        output.push(')');
+    }
+}
+
+fn write_range(value: &str, operand: char, start: u64, end: u64, output: &mut String) {
+    write!(output, "{0}>={1}&&{0}<={2}", value, start, end).unwrap();
+
+    // only `n` can be a fraction
+    if operand == 'n' {
+        write!(output, "&&{}%1===0", value).unwrap();
+    }
+}
+
+/// A plural rule in the disjunctive normal form.
+type Condition = Vec<Vec<Relation>>;
+
+fn parse_range_value(s: &str) -> u64 {
+    s.trim().parse().unwrap()
+}
+
+/// Parse an embedded rule, such as `v = 0 and i % 10 = 1 or f % 10 = 1`.
+fn parse_condition(rule: &str) -> Condition {
+    rule.split(" or ")
+        .map(|and_condition| {
+            and_condition
+                .split(" and ")
+                .map(|relation| {
+                    let (expression, negated, ranges) = match relation.split_once("!=") {
+                        Some((expression, ranges)) => (expression, true, ranges),
+                        None => {
+                            let (expression, ranges) = relation.split_once('=').unwrap();
+
+                            (expression, false, ranges)
+                        },
+                    };
+
+                    let (operand, modulus) = match expression.split_once('%') {
+                        Some((operand, modulus)) => (operand, Some(parse_range_value(modulus))),
+                        None => (expression, None),
+                    };
+
+                    let ranges = ranges
+                        .split(',')
+                        .map(|range| match range.split_once("..") {
+                            Some((start, end)) => {
+                                (parse_range_value(start), parse_range_value(end))
+                            },
+                            None => {
+                                let value = parse_range_value(range);
+
+                                (value, value)
+                            },
+                        })
+                        .collect();
+
+                    Relation {
+                        operand: operand.trim().chars().next().unwrap(),
+                        modulus,
+                        negated,
+                        ranges,
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// The cardinal plural rules of a locale, which are embedded from CLDR.
+#[derive(Debug, Clone)]
+pub struct PluralRules {
+    rules: Vec<(PluralCategory, Condition)>,
+}
+
+impl PluralRules {
+    /// Get the plural rules of a locale, such as `en`, `pt-PT` or `zh_Hant`. The language subtag is used if there are no rules for the whole locale, and only the `other` category is used for unknown languages.
+    pub fn for_locale<L: AsRef<str>>(locale: L) -> PluralRules {
+        let locale = locale.as_ref().replace('_', "-").to_ascii_lowercase();
+
+        let language = locale.split('-').next().unwrap_or("");
+
+        let find = |tag: &str| PLURAL_RULES.iter().find(|(locales, _)| locales.contains(&tag));
+
+        let rules = match find(&locale).or_else(|| find(language)) {
+            Some((_, rules)) => rules
+                .iter()
+                .map(|(category, rule)| {
+                    (PluralCategory::from_name(category).unwrap(), parse_condition(rule))
+                })
+                .collect(),
+            None => Vec::new(),
+        };
+
+        PluralRules {
+            rules,
+        }
+    }
+
+    /// The categories used by the locale, ending with `PluralCategory::Other`.
+    pub fn categories(&self) -> Vec<PluralCategory> {
+        let mut categories: Vec<PluralCategory> =
+            self.rules.iter().map(|(category, _)| *category).collect();
+
+        categories.push(PluralCategory::Other);
+
+        categories
+    }
+
+    /// Select the plural category of a number.
+    pub fn select(&self, n: f64) -> PluralCategory {
+        let operands = Operands::new(n);
+
+        self.rules
+            .iter()
+            .find(|(_, condition)| {
+                condition.iter().any(|relations| relations.iter().all(|r| r.matches(&operands)))
+            })
+            .map_or(PluralCategory::Other, |(category, _)| *category)
+    }
+
+    /// Generate a JavaScript function which takes a number and returns the name of its plural category, such as `function(n){...return 'one';...}`. The code is safe to be used in HTML.
+    pub fn to_javascript(&self) -> String {
+        if self.rules.is_empty() {
+            return String::from("function(){return 'other';}");
+        }
+
+        let used = |operand: char| {
+            self.rules
+                .iter()
+                .flat_map(|(_, condition)| condition.iter().flatten())
+                .any(|r| r.operand == operand)
+        };
+
+        let mut variables = Vec::new();
+
+        if used('v') || used('w') || used('f') || used('t') {
+            variables.push("s=String(n).split('.')[1]||''");
+        }
+
+        if used('w') || used('t') {
+            variables.push("r=s.replace(/0+$/,'')");
+        }
+
+        for (operand, variable) in [
+            ('i', "i=Math.floor(n)"),
+            ('v', "v=s.length"),
+            ('w', "w=r.length"),
+            ('f', "f=+s"),
+            ('t', "t=+r"),
+            ('e', "e=0"),
+        ] {
+            if used(operand) {
+                variables.push(variable);
+            }
+        }
+
+        let mut code = String::from("function(n){n=Math.abs(n);");
+
+        if !variables.is_empty() {
+            code.push_str("var ");
+            code.push_str(&variables.join(","));
+            code.push(';');
+        }
+
+        code.push_str("return ");
+
+        for (category, condition) in self.rules.iter() {
+            for (i, relations) in condition.iter().enumerate() {
+                if i > 0 {
+                    code.push_str("||");
+                }
+
+                for (j, relation) in relations.iter().enumerate() {
+                    if j > 0 {
+                        code.push_str("&&");
+                    }
+
+                    relation.write_javascript(&mut code);
+                }
+            }
+
+            write!(code, "?'{}':", category).unwrap();
+        }
+
+        code.push_str("'other';}");
+
+        code
+    }
+}
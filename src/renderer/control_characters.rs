@@ -0,0 +1,16 @@
+/// What to do with the control characters in the keys and the strings of the values, which are the C0 and C1 control characters, `DEL`, and the line separators `U+2028` and `U+2029`. Tabs, line feeds and carriage returns are ordinary text, so they are always escaped as `\t`, `\n` and `\r`. The policy applies to the keys of the entries and the keys and strings in their values, recursively.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ControlCharacterPolicy {
+    /// Escape the characters as `\b`, `\f` or `\uXXXX`, such as `\u0000` and `\u2028`. It is what happens without a policy.
+    Escape,
+    /// Fail with `RenderError::ControlCharacter`.
+    Reject,
+    /// Remove the characters.
+    Strip,
+}
+
+/// Check whether a character is handled by `ControlCharacterPolicy`.
+#[inline]
+pub(crate) fn is_control_character(c: char) -> bool {
+    (c.is_control() && !matches!(c, '\t' | '\n' | '\r')) || matches!(c, '\u{2028}' | '\u{2029}')
+}
@@ -0,0 +1,96 @@
+use alloc::{string::String, vec::Vec};
+use core::fmt::{self, Display, Formatter};
+#[cfg(feature = "std")]
+use std::{error::Error, io};
+
+/// Errors which can occur when rendering a map with a `Renderer`.
+#[derive(Debug)]
+pub enum RenderError {
+    /// A placeholder in the value of `key` has a name which is not a JavaScript identifier.
+    InvalidPlaceholder { key: String, name: String },
+    /// The key `name`, which is `key` or a key of an object in the value of `key`, can pollute prototypes.
+    UnsafeKey { key: String, name: String },
+    /// The key `key` or a key or string in its value has a control character, which is rejected by `ControlCharacterPolicy::Reject`.
+    ControlCharacter { key: String },
+    /// More than one entry has each of the keys, which are sorted.
+    KeyCollision { keys: Vec<String> },
+    /// A string in the value of `key` looks like a secret. The value is not included so that it does not leak into logs.
+    SecretDetected { key: String },
+    /// The output would be `size` bytes, which is more than the `limit` of `SizeLimitPolicy::Reject`.
+    SizeLimitExceeded { limit: usize, size: usize },
+    /// A segment of the dotted path of a variable is not an identifier.
+    InvalidVariablePath { path: String },
+    /// The `merge` option of a `ScriptBuilder` is used with `Immutability::DefineProperty`, whose read-only properties cannot be defined again by another fragment.
+    ReadOnlyMerge,
+    /// The renderer of a `ScriptBuilder` uses `SizeLimitPolicy::Truncate`, whose dropped keys could not be returned.
+    TruncatedScript,
+    #[cfg(feature = "std")]
+    IOError(io::Error),
+}
+
+#[cfg(feature = "std")]
+impl From<io::Error> for RenderError {
+    #[inline]
+    fn from(error: io::Error) -> Self {
+        RenderError::IOError(error)
+    }
+}
+
+impl Display for RenderError {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            RenderError::InvalidPlaceholder {
+                key,
+                name,
+            } => {
+                write!(
+                    f,
+                    "the placeholder {:?} in the value of {:?} is not an identifier",
+                    name, key
+                )
+            },
+            RenderError::UnsafeKey {
+                key,
+                name,
+            } => {
+                if key == name {
+                    write!(f, "the key {:?} is unsafe", key)
+                } else {
+                    write!(f, "the key {:?} in the value of {:?} is unsafe", name, key)
+                }
+            },
+            RenderError::ControlCharacter {
+                key,
+            } => write!(f, "the key {:?} or its value has a control character", key),
+            RenderError::KeyCollision {
+                keys,
+            } => write!(f, "more than one entry has the keys {:?}", keys),
+            RenderError::SecretDetected {
+                key,
+            } => write!(f, "the value of {:?} looks like a secret", key),
+            RenderError::SizeLimitExceeded {
+                limit,
+                size,
+            } => write!(
+                f,
+                "the output would be {} bytes, more than the limit of {} bytes",
+                size, limit
+            ),
+            RenderError::InvalidVariablePath {
+                path,
+            } => write!(f, "the variable path {:?} is not a dotted path of identifiers", path),
+            RenderError::ReadOnlyMerge => f.write_str(
+                "read-only properties cannot be merged into the objects of other fragments",
+            ),
+            RenderError::TruncatedScript => f.write_str(
+                "the entries of a script fragment cannot be truncated by the size limit",
+            ),
+            #[cfg(feature = "std")]
+            RenderError::IOError(error) => Display::fmt(error, f),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for RenderError {}
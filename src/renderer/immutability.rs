@@ -0,0 +1,8 @@
+/// How the rendered objects are protected from being changed by other scripts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Immutability {
+    /// Freeze the objects by `Object.freeze`, including the arrays and objects in the values.
+    Freeze,
+    /// Define every entry by `Object.defineProperty` as a non-writable and non-configurable property, so the object can still get new entries. The arrays and objects in the values are frozen.
+    DefineProperty,
+}
@@ -0,0 +1,23 @@
+use alloc::vec::Vec;
+use core::iter::repeat;
+
+/// The indentation of the pretty-printed output of a `Renderer`, for each level of the arrays and objects.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Indentation {
+    /// Indent by a number of spaces.
+    Spaces(usize),
+    /// Indent by a tab.
+    Tab,
+}
+
+impl Indentation {
+    /// Write a line feed followed by the indentation of a level.
+    pub(crate) fn push_new_line(self, depth: usize, output: &mut Vec<u8>) {
+        output.push(b'\n');
+
+        match self {
+            Indentation::Spaces(n) => output.extend(repeat(b' ').take(n * depth)),
+            Indentation::Tab => output.extend(repeat(b'\t').take(depth)),
+        }
+    }
+}
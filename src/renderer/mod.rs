@@ -0,0 +1,1189 @@
+mod control_characters;
+mod error;
+mod immutability;
+mod indentation;
+mod placeholder;
+mod redaction;
+mod script;
+mod size_limit;
+mod string_table;
+mod unsafe_keys;
+
+use alloc::{borrow::Cow, collections::BTreeSet, string::String, vec::Vec};
+use core::{fmt::Display, str::from_utf8_unchecked};
+#[cfg(feature = "std")]
+use std::io::Write;
+
+pub use control_characters::*;
+pub use error::*;
+pub use immutability::*;
+pub use indentation::*;
+pub use placeholder::*;
+pub use redaction::*;
+pub use script::*;
+pub use size_limit::*;
+use string_table::StringTable;
+pub use unsafe_keys::*;
+
+use crate::{
+    escape,
+    function::{compile_function, is_identifier, is_index},
+    json_text::push_json_string,
+    JavaScriptEntries, JsValue,
+};
+
+/// Rendering maps to JavaScript code in HTML with options. With the default options, the output is the same as the output of `MapToJavaScriptHTML`.
+///
+/// ```
+/// use std::collections::BTreeMap;
+///
+/// use map_to_javascript_html::{PlaceholderSyntax, Renderer};
+///
+/// let mut map = BTreeMap::new();
+///
+/// map.insert("hello", "Hello {name}!");
+///
+/// let renderer = Renderer::new().placeholders(PlaceholderSyntax::Braces);
+///
+/// assert_eq!(
+///     "_text['hello']=function(p){return 'Hello '+p.name+'!';};",
+///     renderer.render(&map, "_text").unwrap()
+/// );
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Renderer {
+    placeholders: Option<PlaceholderSyntax>,
+    immutability: Option<Immutability>,
+    unsafe_keys:  Option<UnsafeKeyPolicy>,
+    control:      Option<ControlCharacterPolicy>,
+    collisions:   bool,
+    redaction:    Option<Redaction>,
+    ascii_only:   bool,
+    minify:       bool,
+    pretty:       Option<Indentation>,
+    strings:      Option<usize>,
+    size_limit:   Option<(usize, SizeLimitPolicy)>,
+    value_length: Option<usize>,
+}
+
+impl Renderer {
+    /// Create a renderer with the default options.
+    #[inline]
+    pub const fn new() -> Renderer {
+        Renderer {
+            placeholders: None,
+            immutability: None,
+            unsafe_keys:  None,
+            control:      None,
+            collisions:   false,
+            redaction:    None,
+            ascii_only:   false,
+            minify:       false,
+            pretty:       None,
+            strings:      None,
+            size_limit:   None,
+            value_length: None,
+        }
+    }
+
+    /// Compile the string values which contain placeholders into functions which take an object of parameters `p`, such as `function(p){return 'Hello '+p.name+'!';}`. Their literal parts are escaped in the same way as other string values, and every placeholder name must be a JavaScript identifier or an array index.
+    #[inline]
+    pub fn placeholders(mut self, syntax: PlaceholderSyntax) -> Self {
+        self.placeholders = Some(syntax);
+
+        self
+    }
+
+    /// Protect the rendered entries from being changed by other scripts. With `Immutability::Freeze`, the object of the variable is frozen after its entries, such as `_text['hello']='Hello!';Object.freeze(_text);`, so nothing can be added to it later. With `Immutability::DefineProperty`, every entry is defined as a read-only property, such as `Object.defineProperty(_text,'hello',{value:'Hello!',enumerable:true});`.
+    #[inline]
+    pub fn immutable(mut self, immutability: Immutability) -> Self {
+        self.immutability = Some(immutability);
+
+        self
+    }
+
+    /// Handle the keys which can pollute prototypes, such as `__proto__`, by a policy. It is useful if the keys come from untrusted sources.
+    #[inline]
+    pub fn unsafe_keys(mut self, policy: UnsafeKeyPolicy) -> Self {
+        self.unsafe_keys = Some(policy);
+
+        self
+    }
+
+    /// Handle the control characters in the keys and values, such as `NUL` and `U+2028`, by a policy. Without a policy, they are escaped.
+    #[inline]
+    pub fn control_characters(mut self, policy: ControlCharacterPolicy) -> Self {
+        self.control = Some(policy);
+
+        self
+    }
+
+    /// Fail with `RenderError::KeyCollision` if more than one entry has the same key, which would be assigned more than once with the last value winning. It can happen if different keys of a map have the same `Display` output, or the same key is given more than once to the methods with keys.
+    #[inline]
+    pub fn detect_collisions(mut self, detect_collisions: bool) -> Self {
+        self.collisions = detect_collisions;
+
+        self
+    }
+
+    /// Drop or mask the entries whose keys match the patterns of a `Redaction`, including the entries of nested objects, and optionally fail if a value looks like a secret.
+    #[inline]
+    pub fn redact(mut self, redaction: Redaction) -> Self {
+        self.redaction = Some(redaction);
+
+        self
+    }
+
+    /// Escape every non-ASCII character of the output as `\uXXXX`, such as `'\u4e2d'` for `'中'` and `'\ud83d\ude00'` for `'😀'`, so the output is pure 7-bit ASCII whatever the charset of the page is. Non-ASCII characters of the variable name are escaped in the same way, which is still the same identifier in JavaScript.
+    #[inline]
+    pub fn ascii_only(mut self, ascii_only: bool) -> Self {
+        self.ascii_only = ascii_only;
+
+        self
+    }
+
+    /// Shrink the output further. A key which is an identifier is written in dot notation, such as `_text.hello=`, a key which is an array index is written as a number, such as `_text[1]=`, and each string is quoted by `'` or `"`, whichever needs fewer escapes. The output is as safe in HTML as without this option.
+    #[inline]
+    pub fn minify(mut self, minify: bool) -> Self {
+        self.minify = minify;
+
+        self
+    }
+
+    /// Pretty-print the output for reading and diffing, such as the catalogs committed to a repository. Every statement is on its own line ending with a line feed, including the last one, and the elements of arrays and objects are on their own lines, indented by `indentation` for each level.
+    #[inline]
+    pub fn pretty(mut self, indentation: Indentation) -> Self {
+        self.pretty = Some(indentation);
+
+        self
+    }
+
+    /// Write each string value which is used by more than one entry and has at least `min_length` characters only once. The entries are wrapped in a function which takes an array of the strings and references them by their indices, such as `(function(s){_text['a']=s[0];_text['b']=s[0];})(['Read the terms before continuing.']);`. Nothing is wrapped if no string is used more than once, or if the wrapper and the references would make the output larger than writing every string. The object literals of a `ScriptBuilder` are not deduplicated.
+    #[inline]
+    pub fn deduplicate_strings(mut self, min_length: usize) -> Self {
+        self.strings = Some(min_length);
+
+        self
+    }
+
+    /// Cap the bytes written by rendering a map at `max_bytes`, so a runaway map cannot produce a huge script. If the output would be longer, `SizeLimitPolicy::Reject` fails with `RenderError::SizeLimitExceeded` before anything is written, and `SizeLimitPolicy::Truncate` stops at an entry boundary, writing the first entries which fit and dropping the rest. A `ScriptBuilder` applies the limit to each variable separately, does not limit its object literals, and rejects `SizeLimitPolicy::Truncate`.
+    #[inline]
+    pub fn size_limit(mut self, max_bytes: usize, policy: SizeLimitPolicy) -> Self {
+        self.size_limit = Some((max_bytes, policy));
+
+        self
+    }
+
+    /// Truncate each string value which has more than `max_length` characters, such as `'Lorem ipsum dolor…'`, so the value with the ellipsis `…` has `max_length` characters. The length is counted in characters rather than bytes, and an escape sequence in the value, such as `\\n` or `\\u00e9`, counts as the one character it represents and is never split. The strings nested in arrays and objects are not truncated.
+    #[inline]
+    pub fn max_value_length(mut self, max_length: usize) -> Self {
+        self.value_length = Some(max_length);
+
+        self
+    }
+
+    /// Render a map to minified JavaScript code in HTML. Be careful of the `variable_name` which will not be encoded in HTML.
+    #[inline]
+    pub fn render<M: ?Sized + JavaScriptEntries, S: Display>(
+        &self,
+        map: &M,
+        variable_name: S,
+    ) -> Result<String, RenderError> {
+        let mut s = String::new();
+
+        self.render_to_string(map, variable_name, &mut s)?;
+
+        Ok(s)
+    }
+
+    /// Render a map to minified JavaScript code in HTML. Write it to an existing `String` and return a string slice of the written HTML. Nothing is written if an error occurs. Be careful of the `variable_name` which will not be encoded in HTML.
+    #[inline]
+    pub fn render_to_string<'a, M: ?Sized + JavaScriptEntries, S: Display>(
+        &self,
+        map: &M,
+        variable_name: S,
+        output: &'a mut String,
+    ) -> Result<&'a str, RenderError> {
+        Ok(unsafe {
+            from_utf8_unchecked(self.render_to_vec(map, variable_name, output.as_mut_vec())?)
+        })
+    }
+
+    /// Render a map to minified JavaScript code in HTML. Write it to an existing `Vec<u8>` and return a `u8` slice of the written HTML. Nothing is written if an error occurs. Be careful of the `variable_name` which will not be encoded in HTML.
+    #[inline]
+    pub fn render_to_vec<'a, M: ?Sized + JavaScriptEntries, S: Display>(
+        &self,
+        map: &M,
+        variable_name: S,
+        output: &'a mut Vec<u8>,
+    ) -> Result<&'a [u8], RenderError> {
+        let variable_name = format!("{}", variable_name);
+
+        let current_length = output.len();
+
+        self.write_entries_to_vec(&variable_name, map.javascript_entries(), false, output)?;
+
+        Ok(&output[current_length..])
+    }
+
+    /// Render a map like `render_to_vec`, and return the keys of the entries which are dropped by `SizeLimitPolicy::Truncate` because the output would exceed the size limit, in the order of the entries. Nothing is written if an error occurs. Be careful of the `variable_name` which will not be encoded in HTML.
+    #[inline]
+    pub fn render_to_vec_with_dropped_keys<M: ?Sized + JavaScriptEntries, S: Display>(
+        &self,
+        map: &M,
+        variable_name: S,
+        output: &mut Vec<u8>,
+    ) -> Result<Vec<String>, RenderError> {
+        let variable_name = format!("{}", variable_name);
+
+        self.write_entries_to_vec(&variable_name, map.javascript_entries(), false, output)
+    }
+
+    #[cfg(feature = "std")]
+    /// Render a map to minified JavaScript code in HTML. Write it to a writer. Nothing is written if an error occurs before writing. Be careful of the `variable_name` which will not be encoded in HTML.
+    #[inline]
+    pub fn render_to_writer<M: ?Sized + JavaScriptEntries, S: Display, W: Write>(
+        &self,
+        map: &M,
+        variable_name: S,
+        output: &mut W,
+    ) -> Result<(), RenderError> {
+        let mut buffer = Vec::new();
+
+        self.render_to_vec(map, variable_name, &mut buffer)?;
+
+        Ok(output.write_all(&buffer)?)
+    }
+
+    #[cfg(feature = "std")]
+    /// Render a map like `render_to_writer`, and return the keys of the entries which are dropped by `SizeLimitPolicy::Truncate` because the output would exceed the size limit, in the order of the entries. Nothing is written if an error occurs before writing. Be careful of the `variable_name` which will not be encoded in HTML.
+    #[inline]
+    pub fn render_to_writer_with_dropped_keys<
+        M: ?Sized + JavaScriptEntries,
+        S: Display,
+        W: Write,
+    >(
+        &self,
+        map: &M,
+        variable_name: S,
+        output: &mut W,
+    ) -> Result<Vec<String>, RenderError> {
+        let mut buffer = Vec::new();
+
+        let dropped_keys = self.render_to_vec_with_dropped_keys(map, variable_name, &mut buffer)?;
+
+        output.write_all(&buffer)?;
+
+        Ok(dropped_keys)
+    }
+
+    /// Render a map to minified JavaScript code in HTML by given keys. If the key doesn't exist, the output value will be `undefined`. Be careful of the `variable_name` which will not be encoded in HTML.
+    #[inline]
+    pub fn render_with_keys<M: ?Sized + JavaScriptEntries, S: Display, KS: ?Sized + Display>(
+        &self,
+        map: &M,
+        variable_name: S,
+        keys: &[&KS],
+    ) -> Result<String, RenderError> {
+        let mut s = String::new();
+
+        self.render_with_keys_to_string(map, variable_name, keys, &mut s)?;
+
+        Ok(s)
+    }
+
+    /// Render a map to minified JavaScript code in HTML by given keys. Write it to an existing `String` and return a string slice of the written HTML. If the key doesn't exist, the output value will be `undefined`. Nothing is written if an error occurs. Be careful of the `variable_name` which will not be encoded in HTML.
+    #[inline]
+    pub fn render_with_keys_to_string<
+        'a,
+        M: ?Sized + JavaScriptEntries,
+        S: Display,
+        KS: ?Sized + Display,
+    >(
+        &self,
+        map: &M,
+        variable_name: S,
+        keys: &[&KS],
+        output: &'a mut String,
+    ) -> Result<&'a str, RenderError> {
+        Ok(unsafe {
+            from_utf8_unchecked(self.render_with_keys_to_vec(
+                map,
+                variable_name,
+                keys,
+                output.as_mut_vec(),
+            )?)
+        })
+    }
+
+    /// Render a map to minified JavaScript code in HTML by given keys. Write it to an existing `Vec<u8>` and return a `u8` slice of the written HTML. If the key doesn't exist, the output value will be `undefined`. Nothing is written if an error occurs. Be careful of the `variable_name` which will not be encoded in HTML.
+    #[inline]
+    pub fn render_with_keys_to_vec<
+        'a,
+        M: ?Sized + JavaScriptEntries,
+        S: Display,
+        KS: ?Sized + Display,
+    >(
+        &self,
+        map: &M,
+        variable_name: S,
+        keys: &[&KS],
+        output: &'a mut Vec<u8>,
+    ) -> Result<&'a [u8], RenderError> {
+        let current_length = output.len();
+
+        self.render_with_keys_to_vec_with_dropped_keys(map, variable_name, keys, output)?;
+
+        Ok(&output[current_length..])
+    }
+
+    /// Render a map like `render_with_keys_to_vec`, and return the keys of the entries which are dropped by `SizeLimitPolicy::Truncate` because the output would exceed the size limit, in the order of the given keys. Nothing is written if an error occurs. Be careful of the `variable_name` which will not be encoded in HTML.
+    #[inline]
+    pub fn render_with_keys_to_vec_with_dropped_keys<
+        M: ?Sized + JavaScriptEntries,
+        S: Display,
+        KS: ?Sized + Display,
+    >(
+        &self,
+        map: &M,
+        variable_name: S,
+        keys: &[&KS],
+        output: &mut Vec<u8>,
+    ) -> Result<Vec<String>, RenderError> {
+        let variable_name = format!("{}", variable_name);
+
+        let entries = keys.iter().map(|key| {
+            let key = format!("{}", key);
+            let value = map.javascript_entry(&key).unwrap_or(JsValue::Undefined);
+
+            (Cow::Owned(key), value)
+        });
+
+        self.write_entries_to_vec(&variable_name, entries, false, output)
+    }
+
+    #[cfg(feature = "std")]
+    /// Render a map to minified JavaScript code in HTML by given keys. Write it to a writer. If the key doesn't exist, the output value will be `undefined`. Nothing is written if an error occurs before writing. Be careful of the `variable_name` which will not be encoded in HTML.
+    #[inline]
+    pub fn render_with_keys_to_writer<
+        M: ?Sized + JavaScriptEntries,
+        S: Display,
+        W: Write,
+        KS: ?Sized + Display,
+    >(
+        &self,
+        map: &M,
+        variable_name: S,
+        keys: &[&KS],
+        output: &mut W,
+    ) -> Result<(), RenderError> {
+        let mut buffer = Vec::new();
+
+        self.render_with_keys_to_vec(map, variable_name, keys, &mut buffer)?;
+
+        Ok(output.write_all(&buffer)?)
+    }
+
+    #[cfg(feature = "std")]
+    /// Render a map like `render_with_keys_to_writer`, and return the keys of the entries which are dropped by `SizeLimitPolicy::Truncate` because the output would exceed the size limit, in the order of the given keys. Nothing is written if an error occurs before writing. Be careful of the `variable_name` which will not be encoded in HTML.
+    #[inline]
+    pub fn render_with_keys_to_writer_with_dropped_keys<
+        M: ?Sized + JavaScriptEntries,
+        S: Display,
+        W: Write,
+        KS: ?Sized + Display,
+    >(
+        &self,
+        map: &M,
+        variable_name: S,
+        keys: &[&KS],
+        output: &mut W,
+    ) -> Result<Vec<String>, RenderError> {
+        let mut buffer = Vec::new();
+
+        let dropped_keys =
+            self.render_with_keys_to_vec_with_dropped_keys(map, variable_name, keys, &mut buffer)?;
+
+        output.write_all(&buffer)?;
+
+        Ok(dropped_keys)
+    }
+
+    /// Render a map like `render_to_vec` into the object of a variable which a `ScriptBuilder` has created. `null_prototype` means the object has no prototype, which is needed by `UnsafeKeyPolicy::NullPrototype` to keep the unsafe keys of the entries. The keys dropped by the size limit are not returned, so `ScriptBuilder` rejects `SizeLimitPolicy::Truncate`.
+    pub(crate) fn render_variable_to_vec<M: ?Sized + JavaScriptEntries>(
+        &self,
+        map: &M,
+        variable_name: &str,
+        null_prototype: bool,
+        output: &mut Vec<u8>,
+    ) -> Result<(), RenderError> {
+        self.write_entries_to_vec(variable_name, map.javascript_entries(), null_prototype, output)?;
+
+        Ok(())
+    }
+
+    /// Write the entries and return the keys of the entries dropped by the size limit. `null_prototype` means the object of the variable has no prototype.
+    fn write_entries_to_vec<'m>(
+        &self,
+        variable_name: &str,
+        entries: impl Iterator<Item = (Cow<'m, str>, JsValue<'m>)>,
+        null_prototype: bool,
+        output: &mut Vec<u8>,
+    ) -> Result<Vec<String>, RenderError> {
+        let mut collisions = Collisions::default();
+
+        // the entries are written after all of them are transformed, so the strings used more than once are known
+        let mut transformed = Vec::new();
+
+        for (key, value) in entries {
+            let (key, value) = match self.transform_entry(key, value, null_prototype)? {
+                Some(entry) => entry,
+                None => continue,
+            };
+
+            if self.collisions {
+                collisions.insert(&key);
+            }
+
+            transformed.push((key, value));
+        }
+
+        collisions.check()?;
+
+        let current_length = output.len();
+
+        self.write_transformed_entries_to_vec(variable_name, &transformed, output);
+
+        let size = output.len() - current_length;
+
+        let (limit, policy) = match self.size_limit {
+            Some((limit, policy)) if size > limit => (limit, policy),
+            _ => return Ok(Vec::new()),
+        };
+
+        output.truncate(current_length);
+
+        if policy == SizeLimitPolicy::Reject {
+            return Err(RenderError::SizeLimitExceeded {
+                limit,
+                size,
+            });
+        }
+
+        // the largest number of the first entries which fit, assuming the output grows with the entries
+        let fits = |count: usize, output: &mut Vec<u8>| {
+            self.write_transformed_entries_to_vec(variable_name, &transformed[..count], output);
+
+            let fits = output.len() - current_length <= limit;
+
+            output.truncate(current_length);
+
+            fits
+        };
+
+        let kept = if fits(0, output) {
+            let mut fit = 0;
+            let mut exceed = transformed.len();
+
+            while exceed - fit > 1 {
+                let middle = fit + (exceed - fit) / 2;
+
+                if fits(middle, output) {
+                    fit = middle;
+                } else {
+                    exceed = middle;
+                }
+            }
+
+            self.write_transformed_entries_to_vec(variable_name, &transformed[..fit], output);
+
+            fit
+        } else {
+            0
+        };
+
+        Ok(transformed[kept..].iter().map(|(key, _)| String::from(key.as_ref())).collect())
+    }
+
+    /// Write the transformed entries and the statement which freezes the variable, deduplicating the strings if it makes the output smaller.
+    fn write_transformed_entries_to_vec(
+        &self,
+        variable_name: &str,
+        transformed: &[(Cow<str>, JsValue)],
+        output: &mut Vec<u8>,
+    ) {
+        let current_length = output.len();
+
+        let strings = self
+            .strings
+            .and_then(|min_length| StringTable::new(min_length, variable_name, transformed));
+
+        self.write_transformed_entries_with_strings_to_vec(
+            variable_name,
+            transformed,
+            strings.as_ref(),
+            output,
+        );
+
+        if strings.is_some() {
+            // the wrapper and the references can outweigh the strings written only once
+            let mut plain = Vec::new();
+
+            self.write_transformed_entries_with_strings_to_vec(
+                variable_name,
+                transformed,
+                None,
+                &mut plain,
+            );
+
+            if plain.len() <= output.len() - current_length {
+                output.truncate(current_length);
+                output.extend_from_slice(&plain);
+            }
+        }
+    }
+
+    /// Write the transformed entries, the statement which freezes the variable, and the wrapper of the deduplicated strings.
+    fn write_transformed_entries_with_strings_to_vec(
+        &self,
+        variable_name: &str,
+        transformed: &[(Cow<str>, JsValue)],
+        strings: Option<&StringTable>,
+        output: &mut Vec<u8>,
+    ) {
+        let current_length = output.len();
+
+        let style = self.value_style();
+
+        if let Some(strings) = strings {
+            output.extend_from_slice(b"(function(");
+            output.extend_from_slice(strings.parameter().as_bytes());
+            output.extend_from_slice(b"){");
+            end_line(style, output);
+        }
+
+        for (key, value) in transformed.iter() {
+            if self.immutability == Some(Immutability::DefineProperty) {
+                output.extend_from_slice(b"Object.defineProperty(");
+                output.extend_from_slice(variable_name.as_bytes());
+                output.push(b',');
+
+                if style.minify && is_numeric_key(key) {
+                    output.extend_from_slice(key.as_bytes());
+                } else {
+                    write_string(key, style, output);
+                }
+
+                output.push(b',');
+                write_descriptor_to_vec(value, strings, style, 0, output);
+                output.extend_from_slice(b");");
+                end_line(style, output);
+            } else {
+                write_entry(variable_name, key, value, strings, style, output);
+            }
+        }
+
+        if self.immutability == Some(Immutability::Freeze) {
+            output.extend_from_slice(b"Object.freeze(");
+            output.extend_from_slice(variable_name.as_bytes());
+            output.extend_from_slice(b");");
+            end_line(style, output);
+        }
+
+        if let Some(strings) = strings {
+            output.extend_from_slice(b"})([");
+
+            for (i, s) in strings.strings().iter().enumerate() {
+                if i > 0 {
+                    output.push(b',');
+                }
+
+                if let Some(indentation) = style.pretty {
+                    indentation.push_new_line(1, output);
+                }
+
+                write_string(s, style, output);
+            }
+
+            if let Some(indentation) = style.pretty {
+                indentation.push_new_line(0, output);
+            }
+
+            output.extend_from_slice(b"]);");
+            end_line(style, output);
+        }
+
+        if self.ascii_only {
+            escape::encode_ascii_only_to_vec(output, current_length);
+        }
+    }
+
+    /// Render the entries of a map as an object literal, `{'key':value}`. With `Immutability::DefineProperty`, the values are property descriptors for `Object.defineProperties`. With `Immutability::Freeze`, the object literal itself is not frozen. `null_prototype` means the entries are copied to an object without a prototype. Nothing is written if an error occurs.
+    pub(crate) fn render_object_to_vec<M: ?Sized + JavaScriptEntries>(
+        &self,
+        map: &M,
+        null_prototype: bool,
+        output: &mut Vec<u8>,
+    ) -> Result<(), RenderError> {
+        let current_length = output.len();
+
+        let style = self.value_style();
+
+        output.push(b'{');
+
+        let mut first = true;
+
+        let mut collisions = Collisions::default();
+
+        for (key, value) in map.javascript_entries() {
+            let (key, value) = match self.transform_entry(key, value, null_prototype) {
+                Ok(Some(entry)) => entry,
+                Ok(None) => continue,
+                Err(error) => {
+                    output.truncate(current_length);
+
+                    return Err(error);
+                },
+            };
+
+            if self.collisions {
+                collisions.insert(&key);
+            }
+
+            if first {
+                first = false;
+            } else {
+                output.push(b',');
+            }
+
+            if let Some(indentation) = style.pretty {
+                indentation.push_new_line(1, output);
+            }
+
+            write_property_name(&key, style, output);
+
+            if self.immutability == Some(Immutability::DefineProperty) {
+                write_descriptor_to_vec(&value, None, style, 1, output);
+            } else {
+                write_value(&value, style, 1, output);
+            }
+        }
+
+        if let Err(error) = collisions.check() {
+            output.truncate(current_length);
+
+            return Err(error);
+        }
+
+        if let (Some(indentation), false) = (style.pretty, first) {
+            indentation.push_new_line(0, output);
+        }
+
+        output.push(b'}');
+
+        Ok(())
+    }
+
+    /// How the values are written.
+    #[inline]
+    fn value_style(&self) -> ValueStyle {
+        ValueStyle {
+            freeze:         self.immutability.is_some(),
+            null_prototype: self.unsafe_keys == Some(UnsafeKeyPolicy::NullPrototype),
+            minify:         self.minify,
+            pretty:         self.pretty,
+        }
+    }
+
+    /// Apply the options to an entry. `None` means the entry is skipped. `null_prototype` means the object which the entry is assigned to has no prototype.
+    fn transform_entry<'m>(
+        &self,
+        key: Cow<'m, str>,
+        value: JsValue<'m>,
+        null_prototype: bool,
+    ) -> Result<Option<(Cow<'m, str>, JsValue<'m>)>, RenderError> {
+        // control characters are handled first, so a stripped key is checked by the unsafe key policy
+        let key = self.transform_text(None, key)?;
+        let value = self.transform_control_characters(&key, value)?;
+
+        // assigning `__proto__` to an object with a prototype changes the prototype, so the key is rejected
+        if !null_prototype
+            && self.unsafe_keys == Some(UnsafeKeyPolicy::NullPrototype)
+            && is_unsafe_key(&key)
+        {
+            return Err(RenderError::UnsafeKey {
+                key:  String::from(key.as_ref()),
+                name: key.into_owned(),
+            });
+        }
+
+        let key = match self.transform_key(None, key)? {
+            Some(key) => key,
+            None => return Ok(None),
+        };
+
+        let value = match self.redact_value(&key, value) {
+            Some(value) => value,
+            None => return Ok(None),
+        };
+
+        let value = self.transform_value(&key, value)?;
+
+        Ok(Some((key, value)))
+    }
+
+    /// Apply the unsafe key policy to the key of an entry, or a key of an object in the value of `entry_key`. `None` means the key is skipped.
+    fn transform_key<'m>(
+        &self,
+        entry_key: Option<&str>,
+        key: Cow<'m, str>,
+    ) -> Result<Option<Cow<'m, str>>, RenderError> {
+        match self.unsafe_keys {
+            Some(policy) if is_unsafe_key(&key) => match policy {
+                UnsafeKeyPolicy::NullPrototype => Ok(Some(key)),
+                UnsafeKeyPolicy::Reject => Err(RenderError::UnsafeKey {
+                    key:  String::from(entry_key.unwrap_or(&key)),
+                    name: key.into_owned(),
+                }),
+                UnsafeKeyPolicy::Rename(prefix) => {
+                    Ok(Some(Cow::Owned(format!("{}{}", prefix, key))))
+                },
+                UnsafeKeyPolicy::Skip => Ok(None),
+            },
+            _ => Ok(Some(key)),
+        }
+    }
+
+    /// Apply the control character policy to a key or a string of the entry of `entry_key`, or the key of an entry.
+    fn transform_text<'m>(
+        &self,
+        entry_key: Option<&str>,
+        s: Cow<'m, str>,
+    ) -> Result<Cow<'m, str>, RenderError> {
+        match self.control {
+            Some(policy) if s.chars().any(is_control_character) => match policy {
+                ControlCharacterPolicy::Escape => Ok(s),
+                ControlCharacterPolicy::Reject => Err(RenderError::ControlCharacter {
+                    key: String::from(entry_key.unwrap_or(&s)),
+                }),
+                ControlCharacterPolicy::Strip => {
+                    Ok(Cow::Owned(s.chars().filter(|c| !is_control_character(*c)).collect()))
+                },
+            },
+            _ => Ok(s),
+        }
+    }
+
+    /// Apply the control character policy to the keys and strings in the value of an entry, recursively.
+    fn transform_control_characters<'m>(
+        &self,
+        entry_key: &str,
+        value: JsValue<'m>,
+    ) -> Result<JsValue<'m>, RenderError> {
+        match value {
+            JsValue::String(s) => Ok(JsValue::String(self.transform_text(Some(entry_key), s)?)),
+            JsValue::Array(array) if self.control.is_some() => Ok(JsValue::Array(
+                array
+                    .into_iter()
+                    .map(|value| self.transform_control_characters(entry_key, value))
+                    .collect::<Result<_, _>>()?,
+            )),
+            JsValue::Object(object) if self.control.is_some() => Ok(JsValue::Object(
+                object
+                    .into_iter()
+                    .map(|(key, value)| {
+                        Ok((
+                            self.transform_text(Some(entry_key), key)?,
+                            self.transform_control_characters(entry_key, value)?,
+                        ))
+                    })
+                    .collect::<Result<_, RenderError>>()?,
+            )),
+            value => Ok(value),
+        }
+    }
+
+    /// Apply the redaction to the value of a key. `None` means the entry is dropped.
+    #[inline]
+    fn redact_value<'m>(&self, key: &str, value: JsValue<'m>) -> Option<JsValue<'m>> {
+        match &self.redaction {
+            Some(redaction) => redaction.redact(key, value),
+            None => Some(value),
+        }
+    }
+
+    /// Apply the options to the value of an entry.
+    fn transform_value<'m>(
+        &self,
+        key: &str,
+        value: JsValue<'m>,
+    ) -> Result<JsValue<'m>, RenderError> {
+        let value = if self.unsafe_keys.is_some() || self.redaction.is_some() {
+            self.transform_nested_keys(key, value)?
+        } else {
+            value
+        };
+
+        if let Some(redaction) = &self.redaction {
+            if redaction.detects_secret(&value) {
+                return Err(RenderError::SecretDetected {
+                    key: String::from(key)
+                });
+            }
+        }
+
+        let value = match (self.value_length, value) {
+            (Some(max_length), JsValue::String(s)) => {
+                JsValue::String(size_limit::truncate_text(s, max_length))
+            },
+            (_, value) => value,
+        };
+
+        match (&self.placeholders, value) {
+            (Some(syntax), JsValue::String(s)) => match syntax.compile(&s) {
+                Ok(Ok(expression)) => {
+                    Ok(JsValue::Function(compile_function(expression, "", false)))
+                },
+                Ok(Err(text)) if text != *s => Ok(JsValue::String(Cow::Owned(text))),
+                Ok(Err(_)) => Ok(JsValue::String(s)),
+                Err(name) => Err(RenderError::InvalidPlaceholder {
+                    key: String::from(key),
+                    name,
+                }),
+            },
+            (_, value) => Ok(value),
+        }
+    }
+
+    /// Apply the unsafe key policy and the redaction to the keys of the objects in a value, recursively.
+    fn transform_nested_keys<'m>(
+        &self,
+        entry_key: &str,
+        value: JsValue<'m>,
+    ) -> Result<JsValue<'m>, RenderError> {
+        match value {
+            JsValue::Array(array) => Ok(JsValue::Array(
+                array
+                    .into_iter()
+                    .map(|value| self.transform_nested_keys(entry_key, value))
+                    .collect::<Result<_, _>>()?,
+            )),
+            JsValue::Object(object) => {
+                let mut transformed = Vec::with_capacity(object.len());
+
+                for (key, value) in object {
+                    let key = match self.transform_key(Some(entry_key), key)? {
+                        Some(key) => key,
+                        None => continue,
+                    };
+
+                    if let Some(value) = self.redact_value(&key, value) {
+                        transformed.push((key, self.transform_nested_keys(entry_key, value)?));
+                    }
+                }
+
+                Ok(JsValue::Object(transformed))
+            },
+            value => Ok(value),
+        }
+    }
+}
+
+/// The keys which have been written, and the keys which have been written more than once.
+#[derive(Debug, Default)]
+struct Collisions {
+    keys:       BTreeSet<String>,
+    duplicates: BTreeSet<String>,
+}
+
+impl Collisions {
+    #[inline]
+    fn insert(&mut self, key: &str) {
+        if !self.keys.insert(String::from(key)) {
+            self.duplicates.insert(String::from(key));
+        }
+    }
+
+    #[inline]
+    fn check(self) -> Result<(), RenderError> {
+        if self.duplicates.is_empty() {
+            Ok(())
+        } else {
+            Err(RenderError::KeyCollision {
+                keys: self.duplicates.into_iter().collect()
+            })
+        }
+    }
+}
+
+/// Check whether a key can change a prototype or a constructor when it is assigned.
+#[inline]
+pub(crate) fn is_unsafe_key(key: &str) -> bool {
+    matches!(key, "__proto__" | "constructor" | "prototype")
+}
+
+/// The options of writing values.
+#[derive(Debug, Clone, Copy, Default)]
+struct ValueStyle {
+    /// Wrap the arrays and objects in `Object.freeze`.
+    freeze:         bool,
+    /// Create the objects without a prototype, and write `__proto__` as a computed property name so that it is an own property.
+    null_prototype: bool,
+    /// Write keys in dot notation or as numbers if possible, and quote strings by the quote which needs fewer escapes.
+    minify:         bool,
+    /// Write every statement on its own line, and the elements of arrays and objects on their own indented lines.
+    pretty:         Option<Indentation>,
+}
+
+/// Write a line feed after a statement if pretty-printed.
+#[inline]
+fn end_line(style: ValueStyle, output: &mut Vec<u8>) {
+    if style.pretty.is_some() {
+        output.push(b'\n');
+    }
+}
+
+/// Check whether a key can be written as a number, which is an array index of at most 15 digits, so that the number is exact and converted back to the same key.
+#[inline]
+fn is_numeric_key(key: &str) -> bool {
+    key.len() <= 15 && is_index(key)
+}
+
+/// Write a string literal of text, quoted by `'`, or by the quote which needs fewer escapes if minified.
+fn write_string(text: &str, style: ValueStyle, output: &mut Vec<u8>) {
+    let quote = if style.minify { escape::shortest_quote(text) } else { b'\'' };
+
+    output.push(quote);
+    escape::encode_script_quoted_text_to_vec(text, quote, output);
+    output.push(quote);
+}
+
+/// Write an entry as `variable_name['key']=value;`.
+#[inline]
+pub(crate) fn write_entry_to_vec(
+    variable_name: &str,
+    key: &str,
+    value: &JsValue,
+    output: &mut Vec<u8>,
+) {
+    write_entry(variable_name, key, value, None, ValueStyle::default(), output)
+}
+
+fn write_entry(
+    variable_name: &str,
+    key: &str,
+    value: &JsValue,
+    strings: Option<&StringTable>,
+    style: ValueStyle,
+    output: &mut Vec<u8>,
+) {
+    output.extend_from_slice(variable_name.as_bytes());
+
+    if style.minify && is_identifier(key) {
+        output.push(b'.');
+        output.extend_from_slice(key.as_bytes());
+    } else if style.minify && is_numeric_key(key) {
+        output.push(b'[');
+        output.extend_from_slice(key.as_bytes());
+        output.push(b']');
+    } else {
+        output.push(b'[');
+        write_string(key, style, output);
+        output.push(b']');
+    }
+
+    if style.pretty.is_some() {
+        output.extend_from_slice(b" = ");
+    } else {
+        output.push(b'=');
+    }
+
+    write_entry_value(value, strings, style, 0, output);
+    output.push(b';');
+    end_line(style, output);
+}
+
+/// Write a property name of an object literal followed by `:`. `__proto__` is written as a computed property name for objects without a prototype, because it sets the prototype otherwise.
+fn write_property_name(key: &str, style: ValueStyle, output: &mut Vec<u8>) {
+    let computed = style.null_prototype && key == "__proto__";
+
+    if computed {
+        output.push(b'[');
+    }
+
+    if !computed && style.minify && (is_identifier(key) || is_numeric_key(key)) {
+        output.extend_from_slice(key.as_bytes());
+    } else {
+        write_string(key, style, output);
+    }
+
+    if computed {
+        output.push(b']');
+    }
+
+    output.push(b':');
+
+    if style.pretty.is_some() {
+        output.push(b' ');
+    }
+}
+
+/// Write a read-only property descriptor, `{value:value,enumerable:true}`, whose value is deep-frozen.
+fn write_descriptor_to_vec(
+    value: &JsValue,
+    strings: Option<&StringTable>,
+    style: ValueStyle,
+    depth: usize,
+    output: &mut Vec<u8>,
+) {
+    output.extend_from_slice(b"{value:");
+    write_entry_value(
+        value,
+        strings,
+        ValueStyle {
+            freeze: true,
+            ..style
+        },
+        depth,
+        output,
+    );
+    output.extend_from_slice(b",enumerable:true}");
+}
+
+/// Write the value of an entry, which is a reference to the string table if the table has it.
+#[inline]
+fn write_entry_value(
+    value: &JsValue,
+    strings: Option<&StringTable>,
+    style: ValueStyle,
+    depth: usize,
+    output: &mut Vec<u8>,
+) {
+    match strings.and_then(|strings| strings.index(value).map(|index| (strings, index))) {
+        Some((strings, index)) => {
+            output.extend_from_slice(format!("{}[{}]", strings.parameter(), index).as_bytes())
+        },
+        None => write_value(value, style, depth, output),
+    }
+}
+
+/// Write a value as a JavaScript expression in HTML.
+#[inline]
+pub(crate) fn write_value_to_vec(value: &JsValue, output: &mut Vec<u8>) {
+    write_value(value, ValueStyle::default(), 0, output)
+}
+
+/// Write a value as a JavaScript expression in HTML, whose arrays and objects are at the level `depth` if pretty-printed.
+fn write_value(value: &JsValue, style: ValueStyle, depth: usize, output: &mut Vec<u8>) {
+    match value {
+        JsValue::Undefined => output.extend_from_slice(b"undefined"),
+        JsValue::Null => output.extend_from_slice(b"null"),
+        JsValue::Bool(b) => output.extend_from_slice(if *b { b"true" } else { b"false" }),
+        JsValue::Number(n) => output.extend_from_slice(n.as_str().as_bytes()),
+        JsValue::String(s) => write_string(s, style, output),
+        JsValue::Array(_) | JsValue::Object(_) => {
+            let mut json = String::new();
+
+            push_json_value(value, style, depth, &mut json);
+
+            escape::encode_script_to_vec(json, output);
+        },
+        JsValue::Function(f) => output.extend_from_slice(f.as_str().as_bytes()),
+    }
+}
+
+/// Write a line feed followed by the indentation of a level to JSON text if pretty-printed.
+#[inline]
+fn push_new_line(style: ValueStyle, depth: usize, output: &mut String) {
+    if let Some(indentation) = style.pretty {
+        // the indentation is ASCII
+        indentation.push_new_line(depth, unsafe { output.as_mut_vec() });
+    }
+}
+
+/// Write a value as compact JSON text. Values which JSON does not have are written as JavaScript expressions. Some styles also make the text no longer JSON.
+fn push_json_value(value: &JsValue, style: ValueStyle, depth: usize, output: &mut String) {
+    match value {
+        JsValue::Undefined => output.push_str("undefined"),
+        JsValue::Null => output.push_str("null"),
+        JsValue::Bool(b) => output.push_str(if *b { "true" } else { "false" }),
+        JsValue::Number(n) => output.push_str(n.as_str()),
+        JsValue::String(s) => push_json_string(s, output),
+        JsValue::Array(array) => {
+            if style.freeze {
+                output.push_str("Object.freeze(");
+            }
+
+            output.push('[');
+
+            for (i, value) in array.iter().enumerate() {
+                if i > 0 {
+                    output.push(',');
+                }
+
+                push_new_line(style, depth + 1, output);
+                push_json_value(value, style, depth + 1, output);
+            }
+
+            if !array.is_empty() {
+                push_new_line(style, depth, output);
+            }
+
+            output.push(']');
+
+            if style.freeze {
+                output.push(')');
+            }
+        },
+        JsValue::Object(object) => {
+            if style.freeze {
+                output.push_str("Object.freeze(");
+            }
+
+            if style.null_prototype {
+                output.push_str("Object.assign(Object.create(null),");
+            }
+
+            output.push('{');
+
+            for (i, (key, value)) in object.iter().enumerate() {
+                if i > 0 {
+                    output.push(',');
+                }
+
+                push_new_line(style, depth + 1, output);
+
+                if style.null_prototype && key == "__proto__" {
+                    output.push('[');
+                    push_json_string(key, output);
+                    output.push(']');
+                } else {
+                    push_json_string(key, output);
+                }
+
+                output.push(':');
+
+                if style.pretty.is_some() {
+                    output.push(' ');
+                }
+
+                push_json_value(value, style, depth + 1, output);
+            }
+
+            if !object.is_empty() {
+                push_new_line(style, depth, output);
+            }
+
+            output.push('}');
+
+            if style.null_prototype {
+                output.push(')');
+            }
+
+            if style.freeze {
+                output.push(')');
+            }
+        },
+        JsValue::Function(f) => output.push_str(f.as_str()),
+    }
+}
@@ -0,0 +1,254 @@
+use alloc::{string::String, vec::Vec};
+
+use crate::function::{is_identifier, Expression};
+
+/// The syntax of the placeholders in the string values of a map.
+///
+/// A backslash and the character after it are always treated as text, so an escape sequence never starts a placeholder.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlaceholderSyntax {
+    /// `{name}` reads `p.name` and `{0}` reads `p[0]`. `{{` and `}}` are the literal braces.
+    Braces,
+    /// `%s` and `%d` read `p[0]`, `p[1]`, ... in order, `%1$s` reads `p[0]`, and `%(name)s` reads `p.name`. `%%` is the literal percent sign.
+    Printf,
+    /// `open` + `name` + `close`, such as `{{name}}` or `${name}`. Whitespace around the name is ignored. The delimiters cannot be escaped, and there is no placeholder if `open` or `close` is empty.
+    Delimiters { open: String, close: String },
+}
+
+/// The parts of a string value split by placeholders.
+struct Parts {
+    parts: Vec<Expression>,
+    text:  String,
+}
+
+impl Parts {
+    #[inline]
+    fn new() -> Parts {
+        Parts {
+            parts: Vec::new(), text: String::new()
+        }
+    }
+
+    #[inline]
+    fn push_parameter(&mut self, name: &str) {
+        if !self.text.is_empty() {
+            self.parts.push(Expression::Literal(core::mem::take(&mut self.text)));
+        }
+
+        self.parts.push(Expression::Parameter(String::from(name)));
+    }
+
+    fn into_expression(mut self) -> Result<Expression, String> {
+        if self.parts.is_empty() {
+            return Err(self.text);
+        }
+
+        if !self.text.is_empty() {
+            self.parts.push(Expression::Literal(self.text));
+        }
+
+        // make sure that a single parameter is converted to a string
+        if !matches!(self.parts[0], Expression::Literal(_)) {
+            self.parts.insert(0, Expression::Literal(String::new()));
+        }
+
+        Ok(Expression::Concat(self.parts))
+    }
+}
+
+/// Push the escape sequence which starts at `index` and return the index after it.
+#[inline]
+fn push_escape_sequence(value: &str, index: usize, text: &mut String) -> usize {
+    let length = value[index + 1..].chars().next().map_or(0, char::len_utf8) + 1;
+
+    text.push_str(&value[index..index + length]);
+
+    index + length
+}
+
+/// Get the index of the character after the one which starts at `index`.
+#[inline]
+fn next_char_index(value: &str, index: usize) -> usize {
+    index + value[index..].chars().next().map_or(1, char::len_utf8)
+}
+
+#[inline]
+fn is_placeholder_name(name: &str) -> bool {
+    is_identifier(name) || (!name.is_empty() && name.bytes().all(|b| b.is_ascii_digit()))
+}
+
+impl PlaceholderSyntax {
+    /// Split a value by its placeholders. Return `Ok(Err(text))` if there is no placeholder, where `text` is the value with its escaped delimiters unescaped, or `Err(name)` if a placeholder has an invalid name.
+    pub(crate) fn compile(&self, value: &str) -> Result<Result<Expression, String>, String> {
+        let mut parts = Parts::new();
+
+        let bytes = value.as_bytes();
+        let length = bytes.len();
+
+        let mut i = 0;
+
+        match self {
+            PlaceholderSyntax::Braces => {
+                while i < length {
+                    match bytes[i] {
+                        b'\\' => i = push_escape_sequence(value, i, &mut parts.text),
+                        b'{' if bytes.get(i + 1) == Some(&b'{') => {
+                            parts.text.push('{');
+                            i += 2;
+                        },
+                        b'}' if bytes.get(i + 1) == Some(&b'}') => {
+                            parts.text.push('}');
+                            i += 2;
+                        },
+                        b'{' => match value[i + 1..].find('}') {
+                            Some(end) => {
+                                let name = value[i + 1..i + 1 + end].trim();
+
+                                if !is_placeholder_name(name) {
+                                    return Err(String::from(name));
+                                }
+
+                                parts.push_parameter(name);
+
+                                i += end + 2;
+                            },
+                            None => {
+                                parts.text.push_str(&value[i..]);
+                                i = length;
+                            },
+                        },
+                        _ => {
+                            let next = next_char_index(value, i);
+
+                            let end = value[next..]
+                                .find(['\\', '{', '}'])
+                                .map_or(length, |end| next + end);
+
+                            parts.text.push_str(&value[i..end]);
+                            i = end;
+                        },
+                    }
+                }
+            },
+            PlaceholderSyntax::Printf => {
+                let mut sequence = 0;
+
+                while i < length {
+                    match bytes[i] {
+                        b'\\' => i = push_escape_sequence(value, i, &mut parts.text),
+                        b'%' => match bytes.get(i + 1) {
+                            Some(b'%') => {
+                                parts.text.push('%');
+                                i += 2;
+                            },
+                            Some(b's' | b'd') => {
+                                parts.push_parameter(&format!("{}", sequence));
+                                sequence += 1;
+                                i += 2;
+                            },
+                            Some(b'(') => {
+                                let end = value[i + 2..].find(')').map(|end| i + 2 + end);
+
+                                match end {
+                                    Some(end)
+                                        if matches!(bytes.get(end + 1), Some(b's' | b'd')) =>
+                                    {
+                                        let name = &value[i + 2..end];
+
+                                        if !is_identifier(name) {
+                                            return Err(String::from(name));
+                                        }
+
+                                        parts.push_parameter(name);
+
+                                        i = end + 2;
+                                    },
+                                    _ => {
+                                        parts.text.push('%');
+                                        i += 1;
+                                    },
+                                }
+                            },
+                            Some(b'1'..=b'9') => {
+                                let digits = bytes[i + 1..]
+                                    .iter()
+                                    .take_while(|b| b.is_ascii_digit())
+                                    .count();
+
+                                let end = i + 1 + digits;
+
+                                let position = value[i + 1..end].parse::<usize>().ok();
+
+                                match (position, bytes.get(end), bytes.get(end + 1)) {
+                                    (Some(position), Some(b'$'), Some(b's' | b'd')) => {
+                                        parts.push_parameter(&format!("{}", position - 1));
+
+                                        i = end + 2;
+                                    },
+                                    _ => {
+                                        parts.text.push('%');
+                                        i += 1;
+                                    },
+                                }
+                            },
+                            _ => {
+                                parts.text.push('%');
+                                i += 1;
+                            },
+                        },
+                        _ => {
+                            let next = next_char_index(value, i);
+
+                            let end =
+                                value[next..].find(['\\', '%']).map_or(length, |end| next + end);
+
+                            parts.text.push_str(&value[i..end]);
+                            i = end;
+                        },
+                    }
+                }
+            },
+            PlaceholderSyntax::Delimiters {
+                open,
+                close,
+            } => {
+                if open.is_empty() || close.is_empty() {
+                    return Ok(Err(String::from(value)));
+                }
+
+                while i < length {
+                    if bytes[i] == b'\\' {
+                        i = push_escape_sequence(value, i, &mut parts.text);
+                    } else if value[i..].starts_with(open.as_str()) {
+                        let start = i + open.len();
+
+                        match value[start..].find(close.as_str()) {
+                            Some(end) => {
+                                let name = value[start..start + end].trim();
+
+                                if !is_placeholder_name(name) {
+                                    return Err(String::from(name));
+                                }
+
+                                parts.push_parameter(name);
+
+                                i = start + end + close.len();
+                            },
+                            None => {
+                                parts.text.push_str(&value[i..]);
+                                i = length;
+                            },
+                        }
+                    } else {
+                        let c = value[i..].chars().next().unwrap();
+
+                        parts.text.push(c);
+                        i += c.len_utf8();
+                    }
+                }
+            },
+        }
+
+        Ok(parts.into_expression())
+    }
+}
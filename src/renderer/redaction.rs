@@ -0,0 +1,219 @@
+use alloc::{string::String, vec::Vec};
+use core::f64::consts::LN_2;
+
+use crate::JsValue;
+
+/// What to do with the entries whose keys match a pattern of a `Redaction`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RedactionAction {
+    /// Remove the entries.
+    Drop,
+    /// Replace the values with a string, such as `[REDACTED]`.
+    Mask(String),
+}
+
+/// A deny-list of key patterns used to keep sensitive values, such as passwords and API tokens, out of the rendered output. The patterns apply to the keys of the entries and the keys of the objects in their values, recursively.
+///
+/// A pattern is matched against a whole key without case sensitivity, and `*` in it matches any sequence of characters, so `*token*` matches `accessToken` and `csrf_token_name`.
+///
+/// ```
+/// use std::collections::BTreeMap;
+///
+/// use map_to_javascript_html::{Redaction, RedactionAction, Renderer};
+///
+/// let mut config = BTreeMap::new();
+///
+/// config.insert("apiToken", "sk_live_abc123");
+/// config.insert("theme", "dark");
+///
+/// let renderer = Renderer::new().redact(
+///     Redaction::new()
+///         .pattern("*token*")
+///         .action(RedactionAction::Mask("***".into())),
+/// );
+///
+/// assert_eq!(
+///     "_config['apiToken']='***';_config['theme']='dark';",
+///     renderer.render(&config, "_config").unwrap()
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Redaction {
+    patterns: Vec<String>,
+    action:   RedactionAction,
+    strict:   bool,
+}
+
+impl Default for Redaction {
+    #[inline]
+    fn default() -> Self {
+        Redaction::new()
+    }
+}
+
+impl Redaction {
+    /// Create a redaction without patterns, which drops the matching entries.
+    #[inline]
+    pub const fn new() -> Redaction {
+        Redaction {
+            patterns: Vec::new(), action: RedactionAction::Drop, strict: false
+        }
+    }
+
+    /// Create a redaction with the patterns of common sensitive keys, which are `*password*`, `*passwd*`, `*secret*`, `*token*`, `*api_key*`, `*apikey*`, `*private_key*` and `*credential*`.
+    pub fn sensitive() -> Redaction {
+        [
+            "*password*",
+            "*passwd*",
+            "*secret*",
+            "*token*",
+            "*api_key*",
+            "*apikey*",
+            "*private_key*",
+            "*credential*",
+        ]
+        .iter()
+        .fold(Redaction::new(), |redaction, pattern| redaction.pattern(*pattern))
+    }
+
+    /// Add a key pattern.
+    #[inline]
+    pub fn pattern<P: Into<String>>(mut self, pattern: P) -> Self {
+        self.patterns.push(pattern.into());
+
+        self
+    }
+
+    /// Set what to do with the matching entries. The default action is `RedactionAction::Drop`.
+    #[inline]
+    pub fn action(mut self, action: RedactionAction) -> Self {
+        self.action = action;
+
+        self
+    }
+
+    /// Make rendering fail with `RenderError::SecretDetected` if a string value which is not redacted looks like a secret, such as a long random token. The detection is a heuristic based on the Shannon entropy of the string, so it can miss secrets and flag some harmless values.
+    #[inline]
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+
+        self
+    }
+
+    /// Check whether a key matches any of the patterns.
+    #[inline]
+    pub fn is_match(&self, key: &str) -> bool {
+        self.patterns.iter().any(|pattern| glob_match(pattern.as_bytes(), key.as_bytes()))
+    }
+
+    /// Apply the action to the value of a key. `None` means the entry is dropped.
+    pub(crate) fn redact<'a>(&self, key: &str, value: JsValue<'a>) -> Option<JsValue<'a>> {
+        if !self.is_match(key) {
+            return Some(value);
+        }
+
+        match &self.action {
+            RedactionAction::Drop => None,
+            RedactionAction::Mask(mask) => Some(JsValue::String(mask.clone().into())),
+        }
+    }
+
+    /// Check whether the strict mode finds a string which looks like a secret in a value, recursively.
+    pub(crate) fn detects_secret(&self, value: &JsValue) -> bool {
+        self.strict
+            && match value {
+                JsValue::String(s) => is_high_entropy(s),
+                JsValue::Array(array) => array.iter().any(|value| self.detects_secret(value)),
+                JsValue::Object(object) => {
+                    object.iter().any(|(_, value)| self.detects_secret(value))
+                },
+                _ => false,
+            }
+    }
+}
+
+/// Match a pattern whose `*` matches any sequence of bytes, ignoring the ASCII case.
+fn glob_match(pattern: &[u8], s: &[u8]) -> bool {
+    let (mut p, mut i) = (0, 0);
+
+    // the position of the last `*` in the pattern, and the position in `s` it has matched up to
+    let mut backtrack = None;
+
+    while i < s.len() {
+        if p < pattern.len() && pattern[p] == b'*' {
+            backtrack = Some((p, i));
+            p += 1;
+        } else if p < pattern.len() && pattern[p].eq_ignore_ascii_case(&s[i]) {
+            p += 1;
+            i += 1;
+        } else if let Some((star, matched)) = backtrack {
+            backtrack = Some((star, matched + 1));
+            p = star + 1;
+            i = matched + 1;
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|b| *b == b'*')
+}
+
+/// Check whether a string looks like a random token. Hexadecimal strings of at least 32 characters need 3 bits of entropy per character, and strings of at least 20 characters in the Base64 alphabets need 4 bits.
+fn is_high_entropy(s: &str) -> bool {
+    let bytes = s.as_bytes();
+
+    let is_hex = bytes.iter().all(|b| b.is_ascii_hexdigit());
+    let is_base64 = bytes
+        .iter()
+        .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'+' | b'/' | b'=' | b'-' | b'_'));
+
+    let threshold = if is_hex && bytes.len() >= 32 {
+        3.0
+    } else if is_base64 && bytes.len() >= 20 {
+        4.0
+    } else {
+        return false;
+    };
+
+    let mut counts = [0usize; 256];
+
+    for b in bytes {
+        counts[*b as usize] += 1;
+    }
+
+    let length = bytes.len() as f64;
+
+    let entropy: f64 = counts
+        .iter()
+        .filter(|count| **count > 0)
+        .map(|count| {
+            let p = *count as f64 / length;
+
+            -p * log2(p)
+        })
+        .sum();
+
+    entropy >= threshold
+}
+
+/// The base-2 logarithm of a positive normal number, which is not in `core`.
+fn log2(x: f64) -> f64 {
+    // x = m * 2^e, where m is in [1, 2)
+    let bits = x.to_bits();
+    let e = ((bits >> 52) & 0x7FF) as i64 - 1023;
+    let m = f64::from_bits((bits & 0x000F_FFFF_FFFF_FFFF) | 0x3FF0_0000_0000_0000);
+
+    // ln(m) = 2 * atanh(t), where t = (m - 1) / (m + 1) is in [0, 1/3)
+    let t = (m - 1.0) / (m + 1.0);
+    let t2 = t * t;
+
+    let mut term = t;
+    let mut sum = 0.0;
+
+    for k in 0..16 {
+        sum += term / (2 * k + 1) as f64;
+        term *= t2;
+    }
+
+    e as f64 + 2.0 * sum / LN_2
+}
@@ -0,0 +1,390 @@
+use alloc::{string::String, vec::Vec};
+use core::{fmt::Display, iter::once, str::from_utf8_unchecked};
+#[cfg(feature = "std")]
+use std::io::Write;
+
+use super::{is_unsafe_key, Immutability, RenderError, Renderer, SizeLimitPolicy, UnsafeKeyPolicy};
+use crate::{escape, function::is_identifier, JavaScriptEntries};
+
+/// How the variables of a `ScriptBuilder` are declared.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Declaration {
+    /// `var _text={};`
+    Var,
+    /// `let _text={};`
+    Let,
+    /// `const _text={};`
+    Const,
+}
+
+impl Declaration {
+    /// The keyword of the declaration.
+    #[inline]
+    pub const fn as_str(&self) -> &'static str {
+        match self {
+            Declaration::Var => "var",
+            Declaration::Let => "let",
+            Declaration::Const => "const",
+        }
+    }
+}
+
+/// Building one fragment of JavaScript code in HTML from several variables and their maps, such as `_text`, `_config` and `_routes` in the same `<script>` element. Every map is rendered by the same `Renderer`.
+///
+/// The size limit of the renderer applies to the statements of each variable separately, not to the whole fragment, and the `object_literal` option is not limited. `SizeLimitPolicy::Truncate` cannot be used, because the dropped keys would be lost, so building fails with `RenderError::TruncatedScript`.
+///
+/// ```
+/// use std::collections::BTreeMap;
+///
+/// use map_to_javascript_html::{Declaration, ScriptBuilder};
+///
+/// let mut text = BTreeMap::new();
+/// text.insert("hello", "Hello!");
+///
+/// let mut config = BTreeMap::new();
+/// config.insert("theme", "dark");
+///
+/// let script = ScriptBuilder::new()
+///     .declaration(Declaration::Var)
+///     .variable("_text", &text)
+///     .variable("_config", &config)
+///     .build()
+///     .unwrap();
+///
+/// assert_eq!(
+///     "var _text={};_text['hello']='Hello!';var \
+///      _config={};_config['theme']='dark';",
+///     script
+/// );
+/// ```
+#[derive(Clone, Default)]
+pub struct ScriptBuilder<'a> {
+    renderer:       Renderer,
+    declaration:    Option<Declaration>,
+    namespaces:     bool,
+    object_literal: bool,
+    merge:          bool,
+    iife:           bool,
+    variables:      Vec<(String, &'a dyn JavaScriptEntries)>,
+}
+
+impl<'a> ScriptBuilder<'a> {
+    /// Create a builder without variables, which renders with the default options.
+    #[inline]
+    pub fn new() -> ScriptBuilder<'a> {
+        ScriptBuilder::default()
+    }
+
+    /// Render the maps by a `Renderer` with options.
+    #[inline]
+    pub fn renderer(mut self, renderer: Renderer) -> Self {
+        self.renderer = renderer;
+
+        self
+    }
+
+    /// Declare every variable as an empty object before its entries. Without a declaration, the variables must already exist.
+    #[inline]
+    pub fn declaration(mut self, declaration: Declaration) -> Self {
+        self.declaration = Some(declaration);
+
+        self
+    }
+
+    /// Treat every variable name as a dotted path, such as `window.App.i18n.messages`, whose segments must be ASCII JavaScript identifiers. The first segment must be an existing object, such as `window`, and every following level is initialized to an empty object if it does not exist, like `window.App=window.App||{};`. The declaration is only used for the variable names which have one segment.
+    #[inline]
+    pub fn namespaces(mut self, namespaces: bool) -> Self {
+        self.namespaces = namespaces;
+
+        self
+    }
+
+    /// Write the entries of every variable as one object literal, such as `_text={'hello':'Hello!'};`, instead of assigning them one by one.
+    #[inline]
+    pub fn object_literal(mut self, object_literal: bool) -> Self {
+        self.object_literal = object_literal;
+
+        self
+    }
+
+    /// Merge the entries into the object of every variable if it already exists, so several fragments contributing to the same variables can be included alone or in any order. The variables are declared by `var`, unless they are dotted paths, and the declaration option is ignored. With the `object_literal` option, the entries are merged by `Object.assign`, such as `var _text=Object.assign(_text||{},{'hello':'Hello!'});`. Otherwise, they are assigned after `var _text=_text||{};`. If the renderer freezes the objects, the existing entries are copied to a new object which is frozen again, such as `var _text=Object.freeze(Object.assign({},_text,{'hello':'Hello!'}));`. `Immutability::DefineProperty` cannot be merged, because another fragment cannot define the same read-only properties again, so building fails with `RenderError::ReadOnlyMerge`. With the `iife` option, use dotted paths such as `window._text` instead, because the declared variables are local to the function.
+    #[inline]
+    pub fn merge(mut self, merge: bool) -> Self {
+        self.merge = merge;
+
+        self
+    }
+
+    /// Wrap the fragment in an immediately invoked function expression, `(function(){...})();`. The declared variables are then local to the function.
+    #[inline]
+    pub fn iife(mut self, iife: bool) -> Self {
+        self.iife = iife;
+
+        self
+    }
+
+    /// Add a variable and its map. The variables are written in the order they are added. Be careful of the `variable_name` which will not be encoded in HTML.
+    #[inline]
+    pub fn variable<S: Display>(
+        mut self,
+        variable_name: S,
+        map: &'a dyn JavaScriptEntries,
+    ) -> Self {
+        self.variables.push((format!("{}", variable_name), map));
+
+        self
+    }
+
+    /// Build the fragment.
+    #[inline]
+    pub fn build(&self) -> Result<String, RenderError> {
+        let mut s = String::new();
+
+        self.build_to_string(&mut s)?;
+
+        Ok(s)
+    }
+
+    /// Build the fragment. Write it to an existing `String` and return a string slice of the written HTML. Nothing is written if an error occurs.
+    #[inline]
+    pub fn build_to_string<'o>(&self, output: &'o mut String) -> Result<&'o str, RenderError> {
+        Ok(unsafe { from_utf8_unchecked(self.build_to_vec(output.as_mut_vec())?) })
+    }
+
+    /// Build the fragment. Write it to an existing `Vec<u8>` and return a `u8` slice of the written HTML. Nothing is written if an error occurs.
+    pub fn build_to_vec<'o>(&self, output: &'o mut Vec<u8>) -> Result<&'o [u8], RenderError> {
+        // another fragment defining the same read-only property would throw a `TypeError`
+        if self.merge && self.renderer.immutability == Some(Immutability::DefineProperty) {
+            return Err(RenderError::ReadOnlyMerge);
+        }
+
+        // the keys dropped from the variables could not be returned
+        if let Some((_, SizeLimitPolicy::Truncate)) = self.renderer.size_limit {
+            return Err(RenderError::TruncatedScript);
+        }
+
+        let current_length = output.len();
+
+        if self.iife {
+            output.extend_from_slice(b"(function(){");
+            self.end_line(output);
+        }
+
+        for (variable_name, map) in self.variables.iter() {
+            if let Err(error) = self.write_variable_to_vec(variable_name, *map, output) {
+                output.truncate(current_length);
+
+                return Err(error);
+            }
+        }
+
+        if self.iife {
+            output.extend_from_slice(b"})();");
+            self.end_line(output);
+        }
+
+        if self.renderer.ascii_only {
+            escape::encode_ascii_only_to_vec(output, current_length);
+        }
+
+        Ok(&output[current_length..])
+    }
+
+    fn write_variable_to_vec(
+        &self,
+        variable_name: &str,
+        map: &dyn JavaScriptEntries,
+        output: &mut Vec<u8>,
+    ) -> Result<(), RenderError> {
+        let name = variable_name.as_bytes();
+
+        let is_path = self.namespaces && variable_name.contains('.');
+
+        // a frozen object cannot get new entries, so the existing entries are copied to a new object
+        let freeze = self.renderer.immutability == Some(Immutability::Freeze);
+
+        let empty: &[u8] = if self.renderer.unsafe_keys == Some(UnsafeKeyPolicy::NullPrototype) {
+            b"Object.create(null)"
+        } else {
+            b"{}"
+        };
+
+        if self.namespaces {
+            let is_valid = variable_name.split('.').all(|segment| {
+                is_identifier(segment)
+                    && (self.renderer.unsafe_keys.is_none() || !is_unsafe_key(segment))
+            });
+
+            if !is_valid {
+                return Err(RenderError::InvalidVariablePath {
+                    path: String::from(variable_name)
+                });
+            }
+
+            if is_path {
+                // the last level is initialized by the assignment of an object literal
+                let ends = variable_name
+                    .match_indices('.')
+                    .skip(1)
+                    .map(|(index, _)| index)
+                    .chain(once(variable_name.len()))
+                    .filter(|end| !self.object_literal || *end < variable_name.len());
+
+                for end in ends {
+                    let level = &name[..end];
+
+                    output.extend_from_slice(level);
+                    output.push(b'=');
+
+                    if end < name.len() {
+                        output.extend_from_slice(level);
+                        output.extend_from_slice(b"||{};");
+                    } else {
+                        write_existing_object(level, empty, freeze, output);
+                    }
+
+                    self.end_line(output);
+                }
+            }
+        }
+
+        // only `var` can declare a variable again in another fragment
+        let declaration = if is_path {
+            None
+        } else if self.merge {
+            if is_identifier(variable_name) {
+                Some(Declaration::Var)
+            } else {
+                None
+            }
+        } else {
+            self.declaration
+        };
+
+        if let Some(declaration) = declaration {
+            output.extend_from_slice(declaration.as_str().as_bytes());
+            output.push(b' ');
+        }
+
+        if self.object_literal {
+            output.extend_from_slice(name);
+            output.push(b'=');
+
+            match (self.renderer.immutability, self.merge) {
+                (None, false) if empty == b"{}" => {
+                    self.renderer.render_object_to_vec(map, true, output)?;
+                },
+                (None, false) => {
+                    output.extend_from_slice(b"Object.assign(");
+                    output.extend_from_slice(empty);
+                    output.push(b',');
+                    self.renderer.render_object_to_vec(map, true, output)?;
+                    output.push(b')');
+                },
+                (None, true) => {
+                    output.extend_from_slice(b"Object.assign(");
+                    output.extend_from_slice(name);
+                    output.extend_from_slice(b"||");
+                    output.extend_from_slice(empty);
+                    output.push(b',');
+
+                    // the entries are assigned to the existing object, which may have a prototype
+                    self.renderer.render_object_to_vec(map, false, output)?;
+                    output.push(b')');
+                },
+                (Some(Immutability::Freeze), merge) => {
+                    output.extend_from_slice(b"Object.freeze(");
+
+                    if merge || empty != b"{}" {
+                        output.extend_from_slice(b"Object.assign(");
+                        output.extend_from_slice(empty);
+                        output.push(b',');
+
+                        if merge {
+                            output.extend_from_slice(name);
+                            output.push(b',');
+                        }
+
+                        self.renderer.render_object_to_vec(map, true, output)?;
+                        output.push(b')');
+                    } else {
+                        self.renderer.render_object_to_vec(map, true, output)?;
+                    }
+
+                    output.push(b')');
+                },
+                (Some(Immutability::DefineProperty), _) => {
+                    output.extend_from_slice(b"Object.defineProperties(");
+                    output.extend_from_slice(empty);
+                    output.push(b',');
+                    self.renderer.render_object_to_vec(map, true, output)?;
+                    output.push(b')');
+                },
+            }
+
+            output.push(b';');
+            self.end_line(output);
+        } else {
+            if declaration.is_some() || (self.merge && !is_path) {
+                output.extend_from_slice(name);
+                output.push(b'=');
+
+                if self.merge {
+                    write_existing_object(name, empty, freeze, output);
+                } else {
+                    output.extend_from_slice(empty);
+                    output.push(b';');
+                }
+
+                self.end_line(output);
+            }
+
+            // an existing object, which may have a prototype, is reused unless it is copied to be frozen
+            let created = if is_path || self.merge { freeze } else { declaration.is_some() };
+
+            self.renderer.render_variable_to_vec(
+                map,
+                variable_name,
+                created && empty != b"{}",
+                output,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Write a line feed after a statement if the renderer pretty-prints.
+    #[inline]
+    fn end_line(&self, output: &mut Vec<u8>) {
+        if self.renderer.pretty.is_some() {
+            output.push(b'\n');
+        }
+    }
+
+    #[cfg(feature = "std")]
+    /// Build the fragment. Write it to a writer. Nothing is written if an error occurs before writing.
+    #[inline]
+    pub fn build_to_writer<W: Write>(&self, output: &mut W) -> Result<(), RenderError> {
+        let mut buffer = Vec::new();
+
+        self.build_to_vec(&mut buffer)?;
+
+        Ok(output.write_all(&buffer)?)
+    }
+}
+
+/// Write the existing object of a variable followed by `;`, or a new object if it does not exist. If the object will be frozen, the existing entries are copied to a new object.
+fn write_existing_object(name: &[u8], empty: &[u8], freeze: bool, output: &mut Vec<u8>) {
+    if freeze {
+        output.extend_from_slice(b"Object.assign(");
+        output.extend_from_slice(empty);
+        output.push(b',');
+        output.extend_from_slice(name);
+        output.extend_from_slice(b");");
+    } else {
+        output.extend_from_slice(name);
+        output.extend_from_slice(b"||");
+        output.extend_from_slice(empty);
+        output.push(b';');
+    }
+}
@@ -0,0 +1,64 @@
+use alloc::{borrow::Cow, string::String};
+
+/// What to do if the output of rendering a map would be more bytes than the limit of `Renderer::size_limit`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SizeLimitPolicy {
+    /// Fail with `RenderError::SizeLimitExceeded`. Nothing is written.
+    Reject,
+    /// Write only the first entries which fit in the limit and drop the rest. The `_with_dropped_keys` methods of `Renderer`, such as `Renderer::render_to_vec_with_dropped_keys`, return the keys of the dropped entries.
+    Truncate,
+}
+
+/// Truncate a string value, which can have escape sequences, to at most `max_length` characters ending with `…`. An escape sequence counts as the one character it represents, and it is never split.
+pub(crate) fn truncate_text(text: Cow<str>, max_length: usize) -> Cow<str> {
+    let mut length = 0;
+    let mut end = 0;
+
+    // the end of the characters which are kept before the ellipsis
+    let mut kept = 0;
+
+    while end < text.len() {
+        if length == max_length {
+            if max_length == 0 {
+                return Cow::Borrowed("");
+            }
+
+            let mut s = String::with_capacity(kept + '…'.len_utf8());
+
+            s.push_str(&text[..kept]);
+            s.push('…');
+
+            return Cow::Owned(s);
+        }
+
+        end += next_character(&text[end..]).len();
+        length += 1;
+
+        if length + 1 == max_length {
+            kept = end;
+        }
+    }
+
+    text
+}
+
+/// Get the first character of a string value, which is a whole escape sequence if it starts with `\`.
+fn next_character(text: &str) -> &str {
+    if let Some(escape) = text.strip_prefix('\\') {
+        let escaped = if escape.starts_with("u{") {
+            escape.find('}').map_or(escape.len(), |index| index + 1)
+        } else {
+            let count = match escape.chars().next() {
+                Some('u') => 5,
+                Some('x') => 3,
+                _ => 1,
+            };
+
+            escape.char_indices().nth(count).map_or(escape.len(), |(index, _)| index)
+        };
+
+        &text[..1 + escaped]
+    } else {
+        &text[..text.chars().next().map_or(0, char::len_utf8)]
+    }
+}
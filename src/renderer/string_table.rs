@@ -0,0 +1,84 @@
+use alloc::{borrow::Cow, collections::BTreeMap, vec::Vec};
+
+use crate::JsValue;
+
+/// The names of the parameter of the string table. One of them is different from the root identifier of the variable name, which cannot be shadowed.
+const PARAMETERS: [&str; 2] = ["s", "t"];
+
+/// The string values which are used by more than one entry, which are written once into an array and referenced by their indices.
+#[derive(Debug)]
+pub(crate) struct StringTable<'a> {
+    parameter: &'static str,
+    strings:   Vec<&'a str>,
+    indices:   BTreeMap<&'a str, usize>,
+}
+
+impl<'a> StringTable<'a> {
+    /// Collect the string values of the entries which have at least `min_length` characters and are used more than once, in the order of their first use. `None` means there is no such string.
+    pub(crate) fn new<'m: 'a>(
+        min_length: usize,
+        variable_name: &str,
+        entries: &'a [(Cow<'m, str>, JsValue<'m>)],
+    ) -> Option<StringTable<'a>> {
+        let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+
+        for (_, value) in entries {
+            if let JsValue::String(s) = value {
+                if s.chars().count() >= min_length {
+                    *counts.entry(s.as_ref()).or_insert(0) += 1;
+                }
+            }
+        }
+
+        let mut strings = Vec::new();
+        let mut indices = BTreeMap::new();
+
+        for (_, value) in entries {
+            if let JsValue::String(s) = value {
+                let s = s.as_ref();
+
+                if counts.get(s).map_or(false, |count| *count > 1) && !indices.contains_key(s) {
+                    indices.insert(s, strings.len());
+                    strings.push(s);
+                }
+            }
+        }
+
+        if strings.is_empty() {
+            return None;
+        }
+
+        let root = variable_name
+            .split(|c: char| !(c.is_alphanumeric() || c == '_' || c == '$'))
+            .next()
+            .unwrap_or("");
+
+        let parameter = PARAMETERS.iter().find(|parameter| **parameter != root).unwrap();
+
+        Some(StringTable {
+            parameter,
+            strings,
+            indices,
+        })
+    }
+
+    /// The name of the parameter of the function which takes the array of the strings.
+    #[inline]
+    pub(crate) fn parameter(&self) -> &'static str {
+        self.parameter
+    }
+
+    #[inline]
+    pub(crate) fn strings(&self) -> &[&'a str] {
+        &self.strings
+    }
+
+    /// Find the index of a value if it is a string in the table.
+    #[inline]
+    pub(crate) fn index(&self, value: &JsValue) -> Option<usize> {
+        match value {
+            JsValue::String(s) => self.indices.get(s.as_ref()).copied(),
+            _ => None,
+        }
+    }
+}
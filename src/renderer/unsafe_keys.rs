@@ -0,0 +1,12 @@
+/// What to do with the keys which can change a prototype or a constructor when they are assigned, which are `__proto__`, `constructor` and `prototype`. The policy applies to the keys of the entries and the keys of the objects in their values, recursively.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnsafeKeyPolicy {
+    /// Keep the keys, and create the objects without a prototype by `Object.create(null)`, so the keys are only own properties. The object of the variable must also be created by `Object.create(null)`, which is done by a `ScriptBuilder` when it declares the variable without merging, or copies the existing object to freeze it. Otherwise, such as with `Renderer::render` or when a `ScriptBuilder` merges into an existing object, the object of the variable is created by someone else and may have a prototype, so an unsafe key of an entry is rejected as by `UnsafeKeyPolicy::Reject`, while the unsafe keys of the objects in the values are kept.
+    NullPrototype,
+    /// Fail with `RenderError::UnsafeKey`.
+    Reject,
+    /// Prepend a prefix to the keys, such as `_` for `__proto__` to be `___proto__`.
+    Rename(&'static str),
+    /// Skip the keys and their values.
+    Skip,
+}
@@ -1,8 +1,10 @@
-use alloc::{string::String, vec::Vec};
+use alloc::{borrow::Cow, boxed::Box, string::String, vec::Vec};
 use core::{borrow::Borrow, fmt::Display, hash::Hash, str::from_utf8_unchecked};
 #[cfg(feature = "std")]
 use std::io::{self, Write};
 
+use crate::JsValue;
+
 /// Serializing a map to JavaScript code in HTML.
 pub trait MapToJavaScriptHTML<K> {
     /// Convert this map to minified JavaScript code in HTML. Be careful of the `variable_name` which will not be encoded in HTML.
@@ -105,3 +107,27 @@ pub trait MapToJavaScriptHTML<K> {
     where
         K: Borrow<KS>;
 }
+
+/// Providing the entries of a map as JavaScript values. It is used by `Renderer` and can be used as a trait object.
+pub trait JavaScriptEntries {
+    /// Iterate over the entries. The keys are the property names used in JavaScript.
+    fn javascript_entries(&self) -> Box<dyn Iterator<Item = (Cow<'_, str>, JsValue<'_>)> + '_>;
+
+    /// Get the value of an entry by its property name.
+    #[inline]
+    fn javascript_entry(&self, key: &str) -> Option<JsValue<'_>> {
+        self.javascript_entries().find(|(k, _)| k == key).map(|(_, v)| v)
+    }
+}
+
+impl<T: ?Sized + JavaScriptEntries> JavaScriptEntries for &T {
+    #[inline]
+    fn javascript_entries(&self) -> Box<dyn Iterator<Item = (Cow<'_, str>, JsValue<'_>)> + '_> {
+        (**self).javascript_entries()
+    }
+
+    #[inline]
+    fn javascript_entry(&self, key: &str) -> Option<JsValue<'_>> {
+        (**self).javascript_entry(key)
+    }
+}
@@ -0,0 +1,198 @@
+use alloc::{borrow::Cow, string::String, vec::Vec};
+use core::fmt::{self, Display, Formatter};
+
+/// A JavaScript function compiled by this crate. Its code is safe to be used in HTML.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsFunction {
+    pub(crate) code: String,
+}
+
+impl JsFunction {
+    /// The JavaScript code of this function.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        self.code.as_str()
+    }
+}
+
+impl Display for JsFunction {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(&self.code)
+    }
+}
+
+/// A JavaScript number in the form of a JSON number, `NaN`, `Infinity` or `-Infinity`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct JsNumber {
+    literal: String,
+}
+
+impl JsNumber {
+    /// Create a number from its literal. Return `None` if the literal is not a JSON number, `NaN`, `Infinity` or `-Infinity`.
+    #[inline]
+    pub fn parse<S: AsRef<str>>(literal: S) -> Option<JsNumber> {
+        let literal = literal.as_ref();
+
+        if is_number_literal(literal) {
+            Some(JsNumber {
+                literal: String::from(literal)
+            })
+        } else {
+            None
+        }
+    }
+
+    /// The literal of this number.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        self.literal.as_str()
+    }
+
+    /// Convert this number to a float.
+    #[inline]
+    pub fn as_f64(&self) -> f64 {
+        match self.literal.as_str() {
+            "NaN" => f64::NAN,
+            "Infinity" => f64::INFINITY,
+            "-Infinity" => f64::NEG_INFINITY,
+            literal => literal.parse().unwrap(),
+        }
+    }
+}
+
+impl Display for JsNumber {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(&self.literal)
+    }
+}
+
+impl From<f64> for JsNumber {
+    #[inline]
+    fn from(f: f64) -> Self {
+        let literal = if f.is_nan() {
+            String::from("NaN")
+        } else if f.is_infinite() {
+            String::from(if f.is_sign_positive() { "Infinity" } else { "-Infinity" })
+        } else {
+            format!("{}", f)
+        };
+
+        JsNumber {
+            literal,
+        }
+    }
+}
+
+impl From<f32> for JsNumber {
+    #[inline]
+    fn from(f: f32) -> Self {
+        JsNumber::from(f as f64)
+    }
+}
+
+macro_rules! impl_from_integer {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for JsNumber {
+                #[inline]
+                fn from(n: $t) -> Self {
+                    JsNumber {
+                        literal: format!("{}", n),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_from_integer!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+/// A JavaScript value of an entry of a map.
+///
+/// A top-level string is written like the values of a `BTreeMap`, so a backslash in it starts an escape sequence. Strings in arrays and objects are written as JSON strings.
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsValue<'a> {
+    /// `undefined`, such as the value of a key which is not in the map.
+    Undefined,
+    /// `null`.
+    Null,
+    /// `true` or `false`.
+    Bool(bool),
+    /// A number literal.
+    Number(JsNumber),
+    /// A string.
+    String(Cow<'a, str>),
+    /// An array of values.
+    Array(Vec<JsValue<'a>>),
+    /// An object of keys and values, in the order they are written.
+    Object(Vec<(Cow<'a, str>, JsValue<'a>)>),
+    /// A function compiled by this crate, written as its code.
+    Function(JsFunction),
+}
+
+impl<'a> JsValue<'a> {
+    /// Convert this value into a value which owns its data.
+    pub fn into_owned(self) -> JsValue<'static> {
+        match self {
+            JsValue::Undefined => JsValue::Undefined,
+            JsValue::Null => JsValue::Null,
+            JsValue::Bool(b) => JsValue::Bool(b),
+            JsValue::Number(n) => JsValue::Number(n),
+            JsValue::String(s) => JsValue::String(Cow::Owned(s.into_owned())),
+            JsValue::Array(array) => {
+                JsValue::Array(array.into_iter().map(JsValue::into_owned).collect())
+            },
+            JsValue::Object(object) => JsValue::Object(
+                object
+                    .into_iter()
+                    .map(|(k, v)| (Cow::Owned(k.into_owned()), v.into_owned()))
+                    .collect(),
+            ),
+            JsValue::Function(f) => JsValue::Function(f),
+        }
+    }
+}
+
+/// Check whether a string is a JSON number, `NaN`, `Infinity` or `-Infinity`.
+fn is_number_literal(s: &str) -> bool {
+    if matches!(s, "NaN" | "Infinity" | "-Infinity") {
+        return true;
+    }
+
+    let s = s.strip_prefix('-').unwrap_or(s).as_bytes();
+
+    let integer_length = s.iter().take_while(|b| b.is_ascii_digit()).count();
+
+    if integer_length == 0 || (integer_length > 1 && s[0] == b'0') {
+        return false;
+    }
+
+    let mut rest = &s[integer_length..];
+
+    if let Some(fraction) = rest.strip_prefix(b".") {
+        let fraction_length = fraction.iter().take_while(|b| b.is_ascii_digit()).count();
+
+        if fraction_length == 0 {
+            return false;
+        }
+
+        rest = &fraction[fraction_length..];
+    }
+
+    if let Some(exponent) = rest.strip_prefix(b"e").or_else(|| rest.strip_prefix(b"E")) {
+        let exponent =
+            exponent.strip_prefix(b"+").or_else(|| exponent.strip_prefix(b"-")).unwrap_or(exponent);
+
+        let exponent_length = exponent.iter().take_while(|b| b.is_ascii_digit()).count();
+
+        if exponent_length == 0 {
+            return false;
+        }
+
+        rest = &exponent[exponent_length..];
+    }
+
+    rest.is_empty()
+}
@@ -0,0 +1,118 @@
+use core::{
+    fmt::{self, Display, Formatter},
+    str::from_utf8,
+};
+#[cfg(feature = "std")]
+use std::error::Error;
+
+use crate::loaders::parse_statements;
+
+/// A reason why a fragment of JavaScript code is not safe to be put in a `<script>` element. Every offset is a byte offset in the fragment.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Violation {
+    /// `</script` or `<script`, in any case, which can end the `<script>` element or make an HTML parser enter the double-escaped state.
+    ScriptTag { offset: usize },
+    /// `<!--`, which can make an HTML parser enter the escaped state.
+    CommentOpen { offset: usize },
+    /// The statement which starts at `offset` is not one written by this crate, such as when a quote in a text has broken out of a string literal.
+    InvalidStatement { offset: usize },
+}
+
+impl Violation {
+    /// The byte offset of this violation.
+    #[inline]
+    pub fn offset(&self) -> usize {
+        match self {
+            Violation::ScriptTag {
+                offset,
+            }
+            | Violation::CommentOpen {
+                offset,
+            }
+            | Violation::InvalidStatement {
+                offset,
+            } => *offset,
+        }
+    }
+}
+
+impl Display for Violation {
+    #[inline]
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Violation::ScriptTag {
+                offset,
+            } => write!(f, "a script tag is at {}", offset),
+            Violation::CommentOpen {
+                offset,
+            } => write!(f, "an HTML comment is opened at {}", offset),
+            Violation::InvalidStatement {
+                offset,
+            } => write!(f, "the statement at {} is not written by this crate", offset),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for Violation {}
+
+/// Check that a fragment of JavaScript code written by the `MapToJavaScriptHTML` trait is safe to be put in a `<script>` element.
+///
+/// The fragment must contain no `</script`, `<script` or `<!--`, and it must only consist of the statements `variable_name['key']=value;` which `parse_javascript_html` reads, so a text which breaks out of a string literal makes the statement invalid. Every function value must be a function written for the placeholders of a `Renderer`, which only concatenates string literals and parameters. The other output modes of a `Renderer`, such as immutability, pretty-printing or deduplicated strings, and the functions of Fluent messages are not accepted.
+///
+/// ```
+/// use map_to_javascript_html::{verify_script_safe, Violation};
+///
+/// assert_eq!(
+///     Ok(()),
+///     verify_script_safe(br"_text['a']='It\'s <\/script>';", "_text")
+/// );
+///
+/// assert_eq!(
+///     Err(Violation::InvalidStatement {
+///         offset: 0
+///     }),
+///     verify_script_safe(br"_text['a']='It's';", "_text")
+/// );
+/// assert_eq!(
+///     Err(Violation::ScriptTag {
+///         offset: 12
+///     }),
+///     verify_script_safe(b"_text['a']='</script>';", "_text")
+/// );
+/// ```
+pub fn verify_script_safe(script: &[u8], variable_name: &str) -> Result<(), Violation> {
+    for (offset, e) in script.iter().enumerate() {
+        if *e == b'<' {
+            let rest = &script[(offset + 1)..];
+
+            let starts_with_ignore_case = |prefix: &[u8]| {
+                rest.len() >= prefix.len() && rest[..prefix.len()].eq_ignore_ascii_case(prefix)
+            };
+
+            if starts_with_ignore_case(b"/script") || starts_with_ignore_case(b"script") {
+                return Err(Violation::ScriptTag {
+                    offset,
+                });
+            }
+
+            if rest.starts_with(b"!--") {
+                return Err(Violation::CommentOpen {
+                    offset,
+                });
+            }
+        }
+    }
+
+    let text = from_utf8(script).map_err(|error| Violation::InvalidStatement {
+        offset: error.valid_up_to(),
+    })?;
+
+    parse_statements(text, variable_name, true).map_err(|(offset, _)| {
+        Violation::InvalidStatement {
+            offset,
+        }
+    })?;
+
+    Ok(())
+}
@@ -1,6 +1,6 @@
 use std::collections::BTreeMap;
 
-use map_to_javascript_html::MapToJavaScriptHTML;
+use map_to_javascript_html::{parse_javascript_html, JsValue, MapToJavaScriptHTML, Renderer};
 
 #[test]
 fn to_javascript_html() {
@@ -64,7 +64,7 @@ fn to_javascript_html_complex() {
         "<script>alert('Hello world!');</script><script>alert('哈囉，世界！');</script>",
     );
 
-    let expect = r"text['\'中\'文']='<script>alert(\'Hello world!\');<\/script><script>alert(\'哈囉，世界！\');<\/script>';text['script']='<script>alert(\'Hello world!\');<\/script>';text['test-\'3\'']='Test \'3\'!';text['test-1']='Test 1!';text['test-2']='Test 2!';text['test-\'4\'']='Test \'4\'!';";
+    let expect = r"text['\'中\'文']='<\script>alert(\'Hello world!\');<\/script><\script>alert(\'哈囉，世界！\');<\/script>';text['script']='<\script>alert(\'Hello world!\');<\/script>';text['test-\'3\'']='Test \'3\'!';text['test-1']='Test 1!';text['test-2']='Test 2!';text['test-\'4\'']='Test \'4\'!';";
 
     assert_eq!(expect, map.to_javascript_html("text"));
 }
@@ -84,7 +84,7 @@ fn to_javascript_html_to_writer_complex() {
         "<script>alert('Hello world!');</script><script>alert('哈囉，世界！');</script>",
     );
 
-    let expect = r"text['\'中\'文']='<script>alert(\'Hello world!\');<\/script><script>alert(\'哈囉，世界！\');<\/script>';text['script']='<script>alert(\'Hello world!\');<\/script>';text['test-\'3\'']='Test \'3\'!';text['test-1']='Test 1!';text['test-2']='Test 2!';text['test-\'4\'']='Test \'4\'!';";
+    let expect = r"text['\'中\'文']='<\script>alert(\'Hello world!\');<\/script><\script>alert(\'哈囉，世界！\');<\/script>';text['script']='<\script>alert(\'Hello world!\');<\/script>';text['test-\'3\'']='Test \'3\'!';text['test-1']='Test 1!';text['test-2']='Test 2!';text['test-\'4\'']='Test \'4\'!';";
 
     let mut s = String::new();
 
@@ -93,6 +93,19 @@ fn to_javascript_html_to_writer_complex() {
     assert_eq!(expect, s);
 }
 
+#[test]
+fn to_javascript_html_escapes() {
+    let mut map: BTreeMap<&str, &str> = BTreeMap::new();
+
+    map.insert("a", "1\n2\r3");
+    map.insert("b", "<!-- <SCRIPT trailing\\");
+
+    assert_eq!(
+        r"text['a']='1\n2\r3';text['b']='<\!-- <\SCRIPT trailing\\';",
+        map.to_javascript_html("text")
+    );
+}
+
 #[test]
 fn to_javascript_html_with_keys() {
     let mut map: BTreeMap<&str, &str> = BTreeMap::new();
@@ -121,3 +134,48 @@ fn to_javascript_html_with_keys_to_writer() {
         .unwrap();
     assert_eq!("text['test-3']=undefined;", s);
 }
+
+#[test]
+fn escape_line_continuations() {
+    // an escaped line terminator is kept as a line continuation, like before the line terminators were escaped
+    let mut map = BTreeMap::new();
+    map.insert("a", "1\\\n2");
+    map.insert("b", "1\\\r\n2\r\n3");
+    map.insert("c", "1\\\u{2028}2\u{2029}3");
+
+    let script = map.to_javascript_html("text");
+
+    assert_eq!(
+        "text['a']='1\\\n2';text['b']='1\\\r\n2\\r\\n3';text['c']='1\\\u{2028}2\\u20293';",
+        script
+    );
+
+    assert_eq!(
+        vec![
+            (String::from("a"), JsValue::String("12".into())),
+            (String::from("b"), JsValue::String("12\r\n3".into())),
+            (String::from("c"), JsValue::String("12\u{2029}3".into())),
+        ],
+        parse_javascript_html(&script, "text").unwrap()
+    );
+
+    assert_eq!(
+        "text['c']='12\\u20293';",
+        Renderer::new().ascii_only(true).render_with_keys(&map, "text", &["c"]).unwrap()
+    );
+}
+
+#[test]
+fn escape_invalid_escapes() {
+    let mut map = BTreeMap::new();
+    map.insert("\\u", "\\x4 \\u{110000} \\u{} \\u00e \\\\u");
+    map.insert("valid", "\\x41\\u0041\\u{41}\\u{0010FFFF}");
+
+    let script = map.to_javascript_html("text");
+
+    assert_eq!(
+        r"text['\\u']='\\x4 \\u{110000} \\u{} \\u00e \\u';text['valid']='\x41\u0041\u{41}\u{0010FFFF}';",
+        script
+    );
+    assert!(parse_javascript_html(&script, "text").is_ok());
+}
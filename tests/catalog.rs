@@ -0,0 +1,126 @@
+#![cfg(feature = "std")]
+
+use std::collections::BTreeMap;
+
+use map_to_javascript_html::{Catalog, MapToJavaScriptHTML, Renderer};
+
+fn catalog() -> Catalog {
+    let mut en = BTreeMap::new();
+    en.insert("hello", "Hello!");
+    en.insert("bye", "Bye!");
+    en.insert("color", "Color");
+
+    let mut en_gb = BTreeMap::new();
+    en_gb.insert("color", "Colour");
+
+    let mut fr = BTreeMap::new();
+    fr.insert("hello", "Bonjour !");
+    fr.insert("color", "Couleur");
+
+    let mut fr_ca = BTreeMap::new();
+    fr_ca.insert("bye", "Bonsoir !");
+
+    let mut catalog = Catalog::new("en");
+
+    catalog.insert_locale("en", &en);
+    catalog.insert_locale("en-GB", &en_gb);
+    catalog.insert_locale("fr", &fr);
+    catalog.insert_locale("fr_CA", &fr_ca);
+
+    catalog
+}
+
+#[test]
+fn fallback_chain() {
+    let catalog = catalog();
+
+    assert_eq!(vec!["fr_CA", "fr", "en"], catalog.fallback_chain("fr-CA"));
+    assert_eq!(vec!["fr", "en"], catalog.fallback_chain("fr-BE"));
+    assert_eq!(vec!["en-GB", "en"], catalog.fallback_chain("EN_gb"));
+    assert_eq!(vec!["en"], catalog.fallback_chain("en"));
+    assert_eq!(vec!["en"], catalog.fallback_chain("ja-JP"));
+    assert_eq!(vec!["fr", "en"], catalog.fallback_chain("fr-Latn-FR"));
+
+    assert!(Catalog::new("en").fallback_chain("en").is_empty());
+}
+
+#[test]
+fn localize() {
+    let catalog = catalog();
+
+    assert_eq!(
+        "_text['bye']='Bonsoir !';_text['color']='Couleur';_text['hello']='Bonjour !';",
+        catalog.localize("fr-CA").to_javascript_html("_text")
+    );
+
+    assert_eq!(
+        "_text['bye']='Bye!';_text['color']='Colour';_text['hello']='Hello!';",
+        catalog.localize("en-GB").to_javascript_html("_text")
+    );
+
+    assert_eq!(
+        "_text['color']='Couleur';_text['bye']='Bye!';_text['missing']=undefined;",
+        catalog.localize("fr").to_javascript_html_with_keys("_text", &["color", "bye", "missing"])
+    );
+
+    let localized = catalog.localize("fr-CA");
+
+    assert_eq!(
+        localized.to_javascript_html("_text"),
+        Renderer::new().render(&localized, "_text").unwrap()
+    );
+}
+
+#[test]
+fn load_dir() {
+    let dir =
+        std::env::temp_dir().join(format!("map-to-javascript-html-catalog-{}", std::process::id()));
+
+    std::fs::create_dir_all(&dir).unwrap();
+
+    std::fs::write(dir.join("en.properties"), "hello=Hello!\nbye=Bye!\n").unwrap();
+    std::fs::write(dir.join("fr.po"), "msgid \"hello\"\nmsgstr \"Bonjour !\"\n").unwrap();
+    std::fs::write(dir.join("README.txt"), "ignored").unwrap();
+
+    let catalog = Catalog::load_dir(&dir, "en").unwrap();
+
+    assert_eq!(vec!["en", "fr"], catalog.locales().collect::<Vec<_>>());
+
+    assert_eq!(
+        "_text['bye']='Bye!';_text['hello']='Bonjour !';",
+        catalog.localize("fr-FR").to_javascript_html("_text")
+    );
+
+    std::fs::write(dir.join("ja.po"), "msgstr \"\"\n").unwrap();
+
+    let error = Catalog::load_dir(&dir, "en").unwrap_err();
+
+    assert!(error.to_string().contains("ja.po"));
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn load_json() {
+    let dir =
+        std::env::temp_dir().join(format!("map-to-javascript-html-json-{}", std::process::id()));
+
+    std::fs::create_dir_all(&dir).unwrap();
+
+    std::fs::write(dir.join("en.json"), r#"{"count": 1, "hello": "Hello!"}"#).unwrap();
+    std::fs::write(dir.join("de.json"), r#"{"hello": "Hallo!"}"#).unwrap();
+
+    let catalog = Catalog::load_dir(&dir, "en").unwrap();
+
+    assert_eq!(
+        "_text['count']=1;_text['hello']='Hallo!';",
+        catalog.localize("de-AT").to_javascript_html("_text")
+    );
+
+    std::fs::write(dir.join("fr.json"), "[]").unwrap();
+
+    assert!(Catalog::load_dir(&dir, "en").is_err());
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
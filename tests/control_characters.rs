@@ -0,0 +1,203 @@
+use std::collections::BTreeMap;
+
+use map_to_javascript_html::{
+    parse_javascript_html, ControlCharacterPolicy, Declaration, Immutability, JsValue,
+    MapToJavaScriptHTML, PlaceholderSyntax, RenderError, Renderer, ScriptBuilder, UnsafeKeyPolicy,
+};
+
+/// Text with a NUL, a C0 control character, `DEL`, a C1 control character, the line separators, and the characters with short escape sequences.
+const TEXT: &str = "a\0b\u{7}c\u{7f}d\u{85}e\u{2028}f\u{2029}g\th\u{8}i\u{c}j\nk\r";
+
+const ESCAPED: &str = r"a\u0000b\u0007c\u007fd\u0085e\u2028f\u2029g\th\bi\fj\nk\r";
+
+fn check<M: MapToJavaScriptHTML<String>>(map: &M, expected: &str) {
+    assert_eq!(expected, map.to_javascript_html("text"));
+    assert_eq!(expected, map.to_javascript_html_with_keys("text", &[TEXT]));
+
+    #[cfg(feature = "std")]
+    {
+        let mut output = Vec::new();
+        map.to_javascript_html_to_writer("text", &mut output).unwrap();
+        assert_eq!(expected.as_bytes(), output.as_slice());
+    }
+}
+
+#[test]
+fn escape() {
+    let mut map = BTreeMap::new();
+    map.insert(String::from(TEXT), TEXT);
+
+    let expected = format!("text['{}']='{}';", ESCAPED, ESCAPED);
+
+    check(&map, &expected);
+    #[cfg(feature = "std")]
+    check(&map.clone().into_iter().collect::<std::collections::HashMap<_, _>>(), &expected);
+
+    assert_eq!(
+        vec![(String::from(TEXT), JsValue::String(TEXT.into()))],
+        parse_javascript_html(&expected, "text").unwrap()
+    );
+}
+
+#[test]
+fn escape_after_backslash() {
+    let mut map = BTreeMap::new();
+    map.insert("a", "\\\0\\\\\0\\\u{2028}");
+
+    // an escaped line terminator is a line continuation, which is kept as it is
+    assert_eq!("text['a']='\\u0000\\\\\\u0000\\\u{2028}';", map.to_javascript_html("text"));
+}
+
+#[test]
+fn render() {
+    let mut map = BTreeMap::new();
+    map.insert(TEXT, TEXT);
+    map.insert("b", "{name}\0");
+
+    let escaped = format!("text['{}']='{}';", ESCAPED, ESCAPED);
+
+    let renderer = Renderer::new();
+    assert_eq!(
+        format!("{}text['b']='{{name}}\\u0000';", escaped),
+        renderer.render(&map, "text").unwrap()
+    );
+
+    let renderer = Renderer::new().control_characters(ControlCharacterPolicy::Escape);
+    assert_eq!(map.to_javascript_html("text"), renderer.render(&map, "text").unwrap());
+
+    let renderer = Renderer::new().control_characters(ControlCharacterPolicy::Strip);
+    assert_eq!(
+        r"text['abcdefg\thij\nk\r']='abcdefg\thij\nk\r';text['b']='{name}';",
+        renderer.render(&map, "text").unwrap()
+    );
+
+    let renderer =
+        renderer.placeholders(PlaceholderSyntax::Braces).immutable(Immutability::DefineProperty);
+    assert_eq!(
+        "Object.defineProperty(text,'b',{value:function(p){return ''+p.name;},enumerable:true});",
+        renderer.render_with_keys(&map, "text", &["b"]).unwrap()
+    );
+
+    let renderer = Renderer::new().placeholders(PlaceholderSyntax::Braces);
+    assert_eq!(
+        r"text['b']=function(p){return ''+p.name+'\u0000';};",
+        renderer.render_with_keys(&map, "text", &["b"]).unwrap()
+    );
+
+    let renderer = Renderer::new().control_characters(ControlCharacterPolicy::Reject);
+
+    match renderer.render(&map, "text") {
+        Err(RenderError::ControlCharacter {
+            key,
+        }) => assert_eq!(TEXT, key),
+        result => panic!("{:?}", result),
+    }
+
+    match renderer.render_with_keys(&map, "text", &["b"]) {
+        Err(RenderError::ControlCharacter {
+            key,
+        }) => assert_eq!("b", key),
+        result => panic!("{:?}", result),
+    }
+
+    let mut map = BTreeMap::new();
+    map.insert("a", "\t\n\r");
+    assert_eq!(r"text['a']='\t\n\r';", renderer.render(&map, "text").unwrap());
+}
+
+#[test]
+fn strip_unsafe_key() {
+    let mut map = BTreeMap::new();
+    map.insert("__proto\0__", "a");
+
+    let renderer = Renderer::new()
+        .control_characters(ControlCharacterPolicy::Strip)
+        .unsafe_keys(UnsafeKeyPolicy::Reject);
+
+    assert!(matches!(renderer.render(&map, "text"), Err(RenderError::UnsafeKey { .. })));
+}
+
+#[test]
+fn script_builder() {
+    let mut map = BTreeMap::new();
+    map.insert("a", "\0\u{2028}");
+
+    assert_eq!(
+        r"var text={'a':'\u0000\u2028'};",
+        ScriptBuilder::new()
+            .declaration(Declaration::Var)
+            .object_literal(true)
+            .variable("text", &map)
+            .build()
+            .unwrap()
+    );
+
+    assert_eq!(
+        "var text={'a':''};",
+        ScriptBuilder::new()
+            .declaration(Declaration::Var)
+            .object_literal(true)
+            .renderer(Renderer::new().control_characters(ControlCharacterPolicy::Strip))
+            .variable("text", &map)
+            .build()
+            .unwrap()
+    );
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn serde_json_map() {
+    let map = serde_json::json!({ TEXT: TEXT, "b": [TEXT, { TEXT: TEXT }] });
+    let map = map.as_object().unwrap();
+
+    assert_eq!(
+        format!("text['{e}']='{e}';text['b']=[\"{e}\",{{\"{e}\":\"{e}\"}}];", e = ESCAPED),
+        map.to_javascript_html("text")
+    );
+
+    let renderer = Renderer::new().control_characters(ControlCharacterPolicy::Strip);
+    assert_eq!(
+        r#"text['b']=["abcdefg\thij\nk\r",{"abcdefg\thij\nk\r":"abcdefg\thij\nk\r"}];"#,
+        renderer.render_with_keys(map, "text", &["b"]).unwrap()
+    );
+
+    let renderer = Renderer::new().control_characters(ControlCharacterPolicy::Reject);
+    match renderer.render_with_keys(map, "text", &["b"]) {
+        Err(RenderError::ControlCharacter {
+            key,
+        }) => assert_eq!("b", key),
+        result => panic!("{:?}", result),
+    }
+}
+
+#[cfg(feature = "toml")]
+#[test]
+fn toml_table() {
+    let mut nested = toml::Table::new();
+    nested.insert(String::from(TEXT), toml::Value::from(TEXT));
+
+    let mut map = toml::Table::new();
+    map.insert(String::from("a"), toml::Value::from(TEXT));
+    map.insert(String::from("b"), toml::Value::Table(nested));
+
+    assert_eq!(
+        format!("text['a']='{e}';text['b']={{\"{e}\":\"{e}\"}};", e = ESCAPED),
+        map.to_javascript_html("text")
+    );
+}
+
+#[cfg(feature = "serde_yaml")]
+#[test]
+fn serde_yaml_mapping() {
+    let mut nested = serde_yaml::Mapping::new();
+    nested.insert(TEXT.into(), TEXT.into());
+
+    let mut map = serde_yaml::Mapping::new();
+    map.insert("a".into(), TEXT.into());
+    map.insert("b".into(), serde_yaml::Value::Sequence(vec![nested.into()]));
+
+    assert_eq!(
+        format!("text['a']='{e}';text['b']=[{{\"{e}\":\"{e}\"}}];", e = ESCAPED),
+        map.to_javascript_html("text")
+    );
+}
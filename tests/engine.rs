@@ -0,0 +1,462 @@
+#![cfg(feature = "boa_engine")]
+
+use std::collections::{BTreeMap, HashMap};
+
+use map_to_javascript_html::{
+    evaluate_javascript_html, parse_javascript_html, verify_javascript_html, Catalog, Declaration,
+    EngineError, Immutability, Indentation, JsValue, MapToJavaScriptHTML, PlaceholderSyntax,
+    PluralCategory, PluralMessages, Renderer, ScriptBuilder, SizeLimitPolicy, UnsafeKeyPolicy,
+};
+use proptest::{prelude::*, sample::select};
+
+fn map() -> BTreeMap<&'static str, &'static str> {
+    let mut map = BTreeMap::new();
+    map.insert("quote", "It's \"quoted\"");
+    map.insert("again", "It's \"quoted\"");
+    map.insert("terms", "Read the terms before \"continuing\". </script>");
+    map.insert("terms again", "Read the terms before \"continuing\". </script>");
+    map.insert("script", "</script><script><!-- -->");
+    map.insert("lines", "a\nb\r\nc\u{2028}d\u{2029}e\0f\u{7f}\u{85}\t");
+    map.insert("'key'", "中文😀");
+    map.insert("", "");
+    map.insert("0", "\"quoted\"");
+    map.insert("$id", "it's");
+    map
+}
+
+const FRAGMENTS: &[&str] = &[
+    "'",
+    "\"",
+    "`",
+    "</script>",
+    "<SCRIPT",
+    "<!--",
+    "\n",
+    "\r\n",
+    "\u{2028}",
+    "\0",
+    "{name}",
+    "中文😀",
+];
+
+/// Strings without backslashes, which are likely to contain the characters dangerous to JavaScript in HTML.
+fn text() -> impl Strategy<Value = String> {
+    prop::collection::vec(prop_oneof![select(FRAGMENTS).prop_map(String::from), "[^\\\\]*"], 0..6)
+        .prop_map(|parts| parts.concat())
+}
+
+fn declare(variable_name: &str, code: &str) -> String {
+    format!("var {}={{}};{}", variable_name, code)
+}
+
+#[test]
+fn evaluate() {
+    assert_eq!(JsValue::Bool(true), evaluate_javascript_html("", "1+1===2").unwrap());
+    assert_eq!(
+        JsValue::Array(vec![JsValue::Null, JsValue::Undefined, JsValue::String("a".into())]),
+        evaluate_javascript_html("var a=[null,undefined,'a'];", "a").unwrap()
+    );
+
+    assert!(matches!(evaluate_javascript_html("var a='b;", "a"), Err(EngineError::Evaluation(_))));
+    assert!(matches!(evaluate_javascript_html("", "Symbol()"), Err(EngineError::UnsupportedValue)));
+}
+
+#[test]
+fn verify_mismatch() {
+    let map = map();
+
+    let mut other = map.clone();
+    other.insert("quote", "It's");
+
+    let code = declare("_text", &other.to_javascript_html("_text"));
+
+    assert_eq!(
+        Err(EngineError::Mismatch {
+            key: String::from("quote")
+        }),
+        verify_javascript_html(&map, &code, "_text")
+    );
+
+    let code = declare("_text", &map.to_javascript_html_with_keys("_text", &["quote"]));
+
+    assert_eq!(
+        Err(EngineError::Mismatch {
+            key: String::from("")
+        }),
+        verify_javascript_html(&map, &code, "_text")
+    );
+
+    assert_eq!(
+        Err(EngineError::NotAnObject),
+        verify_javascript_html(&map, "var _text=1;", "_text")
+    );
+}
+
+#[test]
+fn b_tree_map() {
+    let map = map();
+
+    verify_javascript_html(&map, &declare("_text", &map.to_javascript_html("_text")), "_text")
+        .unwrap();
+
+    let mut output = Vec::new();
+    map.to_javascript_html_to_writer("_text", &mut output).unwrap();
+
+    verify_javascript_html(&map, &declare("_text", &String::from_utf8(output).unwrap()), "_text")
+        .unwrap();
+
+    let code = declare("_text", &map.to_javascript_html_with_keys("_text", &["quote", "missing"]));
+
+    assert_eq!(
+        JsValue::Object(vec![
+            ("quote".into(), JsValue::String("It's \"quoted\"".into())),
+            ("missing".into(), JsValue::Undefined),
+        ]),
+        evaluate_javascript_html(&code, "_text").unwrap()
+    );
+}
+
+#[test]
+fn hash_map() {
+    let map: HashMap<&str, &str> = map().into_iter().collect();
+
+    verify_javascript_html(&map, &declare("_text", &map.to_javascript_html("_text")), "_text")
+        .unwrap();
+}
+
+#[test]
+fn renderer() {
+    let map = map();
+
+    for renderer in [
+        Renderer::new(),
+        Renderer::new().immutable(Immutability::Freeze),
+        Renderer::new().immutable(Immutability::DefineProperty),
+        Renderer::new().unsafe_keys(UnsafeKeyPolicy::NullPrototype),
+        Renderer::new().ascii_only(true),
+        Renderer::new().minify(true),
+        Renderer::new().minify(true).immutable(Immutability::DefineProperty),
+        Renderer::new().pretty(Indentation::Tab).immutable(Immutability::Freeze),
+        Renderer::new().deduplicate_strings(0),
+        Renderer::new().deduplicate_strings(0).immutable(Immutability::DefineProperty),
+    ] {
+        let code = declare("_text", &renderer.render(&map, "_text").unwrap());
+
+        verify_javascript_html(&map, &code, "_text").unwrap();
+    }
+
+    assert!(Renderer::new()
+        .deduplicate_strings(0)
+        .render(&map, "_text")
+        .unwrap()
+        .starts_with("(function("));
+
+    let code = declare(
+        "_text",
+        &Renderer::new().immutable(Immutability::Freeze).render(&map, "_text").unwrap(),
+    );
+
+    assert_eq!(
+        JsValue::Bool(true),
+        evaluate_javascript_html(&code, "Object.isFrozen(_text)").unwrap()
+    );
+
+    let mut escapes = BTreeMap::new();
+    escapes.insert("a", r"é\x41\u{1F600}\'\\中😀");
+
+    for max_length in 0..12 {
+        let renderer = Renderer::new().max_value_length(max_length);
+
+        let code = declare("_text", &renderer.render(&escapes, "_text").unwrap());
+
+        match evaluate_javascript_html(&code, "_text.a").unwrap() {
+            JsValue::String(s) => assert!(s.chars().count() <= max_length),
+            value => panic!("{:?}", value),
+        }
+    }
+
+    for max_bytes in 0..200 {
+        let renderer = Renderer::new()
+            .size_limit(max_bytes, SizeLimitPolicy::Truncate)
+            .immutable(Immutability::Freeze);
+
+        let mut output = Vec::new();
+
+        let dropped = renderer.render_to_vec_with_dropped_keys(&map, "_text", &mut output).unwrap();
+
+        assert!(output.len() <= max_bytes);
+
+        let code = declare("_text", &String::from_utf8(output).unwrap());
+
+        let keys = format!("Object.keys(_text).length==={}", map.len() - dropped.len());
+
+        assert_eq!(JsValue::Bool(true), evaluate_javascript_html(&code, &keys).unwrap());
+    }
+
+    let mut messages = BTreeMap::new();
+    messages.insert("hello", "Hello {name}, it's {0}!");
+
+    let renderer = Renderer::new().placeholders(PlaceholderSyntax::Braces);
+
+    let code = declare("_text", &renderer.render(&messages, "_text").unwrap());
+
+    assert_eq!(
+        JsValue::String("Hello </script>, it's 1!".into()),
+        evaluate_javascript_html(&code, "_text.hello({name:'<\\/script>',0:1})").unwrap()
+    );
+}
+
+#[test]
+fn script_builder() {
+    let map = map();
+
+    let builder = ScriptBuilder::new().variable("_text", &map);
+
+    verify_javascript_html(&map, &declare("_text", &builder.build().unwrap()), "_text").unwrap();
+
+    for builder in [
+        builder.clone().declaration(Declaration::Var),
+        builder.clone().declaration(Declaration::Const),
+        builder.clone().declaration(Declaration::Let).object_literal(true),
+        builder
+            .clone()
+            .renderer(Renderer::new().immutable(Immutability::Freeze))
+            .object_literal(true),
+        builder
+            .clone()
+            .renderer(Renderer::new().immutable(Immutability::DefineProperty))
+            .object_literal(true),
+        builder.clone().merge(true),
+        builder.clone().declaration(Declaration::Var).renderer(Renderer::new().ascii_only(true)),
+        builder
+            .clone()
+            .declaration(Declaration::Var)
+            .renderer(Renderer::new().minify(true))
+            .object_literal(true),
+    ] {
+        verify_javascript_html(&map, &builder.build().unwrap(), "_text").unwrap();
+    }
+
+    let code =
+        ScriptBuilder::new().namespaces(true).variable("window.App.i18n", &map).build().unwrap();
+
+    verify_javascript_html(&map, &format!("var window=globalThis;{}", code), "App.i18n").unwrap();
+
+    let code = ScriptBuilder::new()
+        .iife(true)
+        .namespaces(true)
+        .variable("window._text", &map)
+        .build()
+        .unwrap();
+
+    verify_javascript_html(&map, &format!("var window=globalThis;{}", code), "_text").unwrap();
+}
+
+#[test]
+fn catalog() {
+    let mut en = BTreeMap::new();
+    en.insert("hello", "Hello!");
+    en.insert("bye", "Bye!");
+
+    let mut fr = BTreeMap::new();
+    fr.insert("hello", "Bonjour !");
+
+    let mut catalog = Catalog::new("en");
+    catalog.insert_locale("en", &en);
+    catalog.insert_locale("fr", &fr);
+
+    let localized = catalog.localize("fr-CA");
+
+    verify_javascript_html(
+        &localized,
+        &declare("_text", &localized.to_javascript_html("_text")),
+        "_text",
+    )
+    .unwrap();
+}
+
+#[test]
+fn plural_messages() {
+    let mut messages = PluralMessages::new("en");
+    messages.insert("items", [(PluralCategory::One, "1 item"), (PluralCategory::Other, "# items")]);
+
+    let code = declare("_text", &messages.to_javascript_html("_text"));
+
+    verify_javascript_html(&messages, &code, "_text").unwrap();
+
+    assert_eq!(
+        JsValue::Array(vec![JsValue::String("1 item".into()), JsValue::String("2 items".into())]),
+        evaluate_javascript_html(&code, "[_text.items(1),_text.items(2)]").unwrap()
+    );
+}
+
+#[cfg(feature = "fluent-syntax")]
+#[test]
+fn fluent_messages() {
+    use map_to_javascript_html::{FluentMessages, FluentOutput};
+
+    let messages = FluentMessages::parse(
+        "emails = { $count ->\n    [0] No emails\n    [one] One email\n   *[other] { $count } \
+         emails\n}\n",
+        "en",
+        FluentOutput::Functions,
+    )
+    .unwrap();
+
+    // the plural variants are selected without `Intl`
+    let code =
+        format!("var Intl=undefined;{}", declare("_text", &messages.to_javascript_html("_text")));
+
+    assert_eq!(
+        JsValue::Array(vec![
+            JsValue::String("No emails".into()),
+            JsValue::String("One email".into()),
+            JsValue::String("1.5 emails".into()),
+            JsValue::String("2 emails".into()),
+        ]),
+        evaluate_javascript_html(
+            &code,
+            "[_text.emails({count:0}),_text.emails({count:1}),_text.emails({count:1.5}),_text.\
+             emails({count:2})]"
+        )
+        .unwrap()
+    );
+}
+
+#[cfg(feature = "serde_json")]
+#[test]
+fn serde_json_map() {
+    let map: serde_json::Map<String, serde_json::Value> = serde_json::from_str(
+        r#"{"a":"It's </script>","b":[1,2.5,-0.5,null,true," "],"c":{"__proto__":{"x":1},"d":{}}}"#,
+    )
+    .unwrap();
+
+    // `"__proto__":` in an object literal sets the prototype instead of a property
+    for code in [map.to_javascript_html("_text"), Renderer::new().render(&map, "_text").unwrap()] {
+        assert_eq!(
+            Err(EngineError::Mismatch {
+                key: String::from("c")
+            }),
+            verify_javascript_html(&map, &declare("_text", &code), "_text")
+        );
+    }
+
+    let code = declare(
+        "_text",
+        &Renderer::new().unsafe_keys(UnsafeKeyPolicy::NullPrototype).render(&map, "_text").unwrap(),
+    );
+
+    verify_javascript_html(&map, &code, "_text").unwrap();
+
+    let code = declare(
+        "_text",
+        &Renderer::new()
+            .unsafe_keys(UnsafeKeyPolicy::NullPrototype)
+            .pretty(Indentation::Spaces(2))
+            .render(&map, "_text")
+            .unwrap(),
+    );
+
+    verify_javascript_html(&map, &code, "_text").unwrap();
+    assert_eq!(JsValue::Bool(true), evaluate_javascript_html(&code, "({}).x===undefined").unwrap());
+}
+
+#[cfg(feature = "toml")]
+#[test]
+fn toml_table() {
+    let map: toml::Table =
+        toml::from_str("a = \"It's </script>\"\nb = [1, 2.5, nan, inf]\n[c]\nd = true\n").unwrap();
+
+    verify_javascript_html(&map, &declare("_text", &map.to_javascript_html("_text")), "_text")
+        .unwrap();
+}
+
+#[cfg(feature = "serde_yaml")]
+#[test]
+fn serde_yaml_mapping() {
+    let map: serde_yaml::Mapping =
+        serde_yaml::from_str("a: It's </script>\nb: [1, 2.5, ~, true]\nc:\n  d: e\n1: one\n")
+            .unwrap();
+
+    verify_javascript_html(&map, &declare("_text", &map.to_javascript_html("_text")), "_text")
+        .unwrap();
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    /// The escaping of arbitrary text is read by the engine as `parse_javascript_html` reads it.
+    #[test]
+    fn same_as_parser(map in prop::collection::btree_map(any::<String>(), any::<String>(), 0..4)) {
+        let text = map.to_javascript_html("_text");
+
+        if let Ok(entries) = parse_javascript_html(&text, "_text") {
+            // the last value of a key is used, as JavaScript does
+            let expected: BTreeMap<String, JsValue> = entries.into_iter().collect();
+
+            match evaluate_javascript_html(&declare("_text", &text), "_text") {
+                Ok(JsValue::Object(object)) => {
+                    let actual: BTreeMap<String, JsValue> =
+                        object.into_iter().map(|(key, value)| (key.into_owned(), value)).collect();
+
+                    prop_assert_eq!(expected, actual);
+                },
+                // lone surrogates from the escape sequences in the text cannot be read
+                Err(EngineError::UnsupportedValue) => (),
+                result => panic!("{:?}", result),
+            }
+        }
+    }
+
+    /// The output modes of a `Renderer` which `verify_script_safe` does not accept are read back by the engine.
+    #[test]
+    fn renderer_modes(
+        map in prop::collection::btree_map(text(), text(), 0..6),
+        immutability in prop_oneof![
+            Just(None),
+            Just(Some(Immutability::Freeze)),
+            Just(Some(Immutability::DefineProperty)),
+        ],
+        null_prototype in any::<bool>(),
+        ascii_only in any::<bool>(),
+        minify in any::<bool>(),
+        pretty in any::<bool>(),
+        deduplicate in any::<bool>(),
+    ) {
+        let mut renderer = Renderer::new().ascii_only(ascii_only).minify(minify);
+
+        if let Some(immutability) = immutability {
+            renderer = renderer.immutable(immutability);
+        }
+
+        if null_prototype {
+            renderer = renderer.unsafe_keys(UnsafeKeyPolicy::NullPrototype);
+        }
+
+        if pretty {
+            renderer = renderer.pretty(Indentation::Spaces(2));
+        }
+
+        if deduplicate {
+            renderer = renderer.deduplicate_strings(0);
+        }
+
+        if let Ok(script) = renderer.render(&map, "_text") {
+            let lowercase = script.to_ascii_lowercase();
+
+            prop_assert!(
+                !lowercase.contains("<script") && !lowercase.contains("</script") && !script.contains("<!--"),
+                "{:?}",
+                script
+            );
+            prop_assert_eq!(Ok(()), verify_javascript_html(&map, &declare("_text", &script), "_text"));
+        }
+    }
+
+    #[test]
+    fn b_tree_map_without_backslashes(map in prop::collection::btree_map("[^\\\\]*", "[^\\\\]*", 0..4)) {
+        let code = declare("_text", &map.to_javascript_html("_text"));
+
+        prop_assert_eq!(Ok(()), verify_javascript_html(&map, &code, "_text"));
+    }
+}
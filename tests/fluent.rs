@@ -0,0 +1,111 @@
+#![cfg(feature = "fluent-syntax")]
+
+use map_to_javascript_html::{FluentMessages, FluentOutput, MapToJavaScriptHTML, ParseErrorKind};
+
+const RESOURCE: &str = r#"
+-brand = { $case ->
+   *[nominative] Firefox
+    [genitive] Firefox's
+}
+
+hello = Hello world!
+welcome = Welcome to { -brand(case: "genitive") } site, { $user-name }!
+emails = { $count ->
+    [0] You have no emails.
+    [one] You have one email.
+   *[other] You have { $count } emails.
+}
+login = Log in
+    .placeholder = { login }, it's </script>
+"#;
+
+#[test]
+fn to_javascript_html_functions() {
+    let messages = FluentMessages::parse(RESOURCE, "en", FluentOutput::Functions).unwrap();
+
+    assert_eq!(5, messages.len());
+    assert_eq!(Some("function(){return 'Hello world!';}"), messages.get("hello"));
+    assert_eq!(
+        Some(r"function(p){p=p||{};return 'Welcome to Firefox\'s site, '+p['user-name']+'!';}"),
+        messages.get("welcome")
+    );
+    assert_eq!(
+        Some(
+            r"function(p){p=p||{};return (function(v){var c=typeof v==='number'?(function(n){n=Math.abs(n);var s=String(n).split('.')[1]||'',i=Math.floor(n),v=s.length;return i===1&&v===0?'one':'other';})(v):'';return v===0||v==='0'?'You have no emails.':v==='one'?'You have one email.':v==='other'?'You have '+p.count+' emails.':c==='one'?'You have one email.':c==='other'?'You have '+p.count+' emails.':'You have '+p.count+' emails.';})(p.count);}"
+        ),
+        messages.get("emails")
+    );
+    assert_eq!(
+        Some(r"function(){return 'Log in, it\'s <\/script>';}"),
+        messages.get("login.placeholder")
+    );
+
+    assert_eq!(
+        "text['hello']=function(){return 'Hello world!';};text['login']=function(){return 'Log \
+         in';};",
+        messages.to_javascript_html_with_keys("text", &["hello", "login"])
+    );
+}
+
+#[test]
+fn to_javascript_html_functions_references() {
+    let messages = FluentMessages::parse(
+        "a = { b } and { missing }\nb = { a }\nc = { NUMBER($n, minimumFractionDigits: 2) }{ \
+         \"\\u0021\" }",
+        "",
+        FluentOutput::Functions,
+    )
+    .unwrap();
+
+    assert_eq!(Some("function(){return '{a} and {missing}';}"), messages.get("a"));
+    assert_eq!(Some("function(p){p=p||{};return ''+p.n+'!';}"), messages.get("c"));
+}
+
+#[test]
+fn to_javascript_html_patterns() {
+    let messages = FluentMessages::parse(RESOURCE, "en", FluentOutput::Patterns).unwrap();
+
+    assert_eq!(
+        r#"text['hello']='Hello world!';text['welcome']='Welcome to { -brand(case: "genitive") } site, { $user-name }!';"#,
+        messages.to_javascript_html_with_keys("text", &["hello", "welcome"])
+    );
+    assert_eq!(
+        Some(
+            r"'{ $count ->\n    [0] You have no emails.\n    [one] You have one email.\n   *[other] You have { $count } emails.\n }'"
+        ),
+        messages.get("emails")
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn to_javascript_html_to_writer() {
+    let messages =
+        FluentMessages::parse("hello = Hello, { $name }!", "en", FluentOutput::Functions).unwrap();
+
+    let mut s = String::new();
+
+    messages.to_javascript_html_to_writer("text", unsafe { s.as_mut_vec() }).unwrap();
+    assert_eq!("text['hello']=function(p){p=p||{};return 'Hello, '+p.name+'!';};", s);
+
+    s.clear();
+    messages
+        .to_javascript_html_with_keys_to_writer("text", &["hello", "other"], unsafe {
+            s.as_mut_vec()
+        })
+        .unwrap();
+    assert_eq!(
+        "text['hello']=function(p){p=p||{};return 'Hello, '+p.name+'!';};text['other']=undefined;",
+        s
+    );
+}
+
+#[test]
+fn parse_errors() {
+    let error =
+        FluentMessages::parse("hello = Hello\n\nwelcome = { $name", "en", FluentOutput::Functions)
+            .unwrap_err();
+
+    assert_eq!(&ParseErrorKind::InvalidSyntax, error.kind());
+    assert_eq!(3, error.line());
+}
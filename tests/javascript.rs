@@ -0,0 +1,208 @@
+use std::collections::BTreeMap;
+
+use map_to_javascript_html::{
+    parse_javascript_html, JsNumber, JsValue, MapToJavaScriptHTML, ParseErrorKind, PluralCategory,
+    PluralMessages,
+};
+use proptest::prelude::*;
+
+#[test]
+fn parse() {
+    let entries = parse_javascript_html(
+        r"_text['a']='x\'y\\z\n<\/script>';_text['b']=undefined;_text['\'c\'']='\u4E2D\u{1F600}\uD83D\uDE00\x41\q\101\0\08';",
+        "_text",
+    )
+    .unwrap();
+
+    assert_eq!(
+        vec![
+            (String::from("a"), JsValue::String("x'y\\z\n</script>".into())),
+            (String::from("b"), JsValue::Undefined),
+            (String::from("'c'"), JsValue::String("中😀😀AqA\0\08".into())),
+        ],
+        entries
+    );
+
+    assert!(parse_javascript_html("", "_text").unwrap().is_empty());
+}
+
+#[test]
+fn parse_values() {
+    let entries = parse_javascript_html(
+        r#"t['a']=null;t['b']=true;t['c']=-1.5e3;t['d']=-Infinity;t['e']=NaN;t['f']=[1,"<\/script>",[],{}];t['g']={"x":false,"y":{"z":"\"\u0001"}};"#,
+        "t",
+    )
+    .unwrap();
+
+    assert_eq!(
+        vec![
+            (String::from("a"), JsValue::Null),
+            (String::from("b"), JsValue::Bool(true)),
+            (String::from("c"), JsValue::Number(JsNumber::parse("-1.5e3").unwrap())),
+            (String::from("d"), JsValue::Number(JsNumber::from(f64::NEG_INFINITY))),
+            (String::from("e"), JsValue::Number(JsNumber::from(f64::NAN))),
+            (
+                String::from("f"),
+                JsValue::Array(vec![
+                    JsValue::Number(1.into()),
+                    JsValue::String("</script>".into()),
+                    JsValue::Array(vec![]),
+                    JsValue::Object(vec![]),
+                ])
+            ),
+            (
+                String::from("g"),
+                JsValue::Object(vec![
+                    ("x".into(), JsValue::Bool(false)),
+                    (
+                        "y".into(),
+                        JsValue::Object(vec![("z".into(), JsValue::String("\"\u{1}".into()))])
+                    ),
+                ])
+            ),
+        ],
+        entries
+    );
+}
+
+#[test]
+fn parse_functions() {
+    let mut messages = PluralMessages::new("en");
+
+    messages
+        .insert("items", [(PluralCategory::One, "1 item"), (PluralCategory::Other, "# items}")]);
+
+    let text = messages.to_javascript_html("_text");
+
+    let entries = parse_javascript_html(&text, "_text").unwrap();
+
+    assert_eq!(1, entries.len());
+    assert_eq!("items", entries[0].0);
+
+    match &entries[0].1 {
+        JsValue::Function(f) => assert_eq!(format!("_text['items']={};", f), text),
+        value => panic!("{:?}", value),
+    }
+}
+
+#[test]
+fn parse_errors() {
+    let error = parse_javascript_html("_text['a']='b';\n_other['c']='d';", "_text").unwrap_err();
+    assert_eq!(&ParseErrorKind::InvalidSyntax, error.kind());
+    assert_eq!(2, error.line());
+    assert_eq!(1, error.column());
+
+    let error = parse_javascript_html("_text['中']='b\nc';", "_text").unwrap_err();
+    assert_eq!(&ParseErrorKind::UnterminatedString, error.kind());
+    assert_eq!(1, error.line());
+    assert_eq!(12, error.column());
+
+    let error = parse_javascript_html(r"_text['a']='\uD800';", "_text").unwrap_err();
+    assert_eq!(&ParseErrorKind::InvalidUnicodeEscape, error.kind());
+
+    let error = parse_javascript_html(r"_text['a']='\x4';", "_text").unwrap_err();
+    assert_eq!(&ParseErrorKind::InvalidEscape, error.kind());
+
+    let error = parse_javascript_html("_text['a']='b' ;", "_text").unwrap_err();
+    assert_eq!(&ParseErrorKind::TrailingCharacters, error.kind());
+    assert_eq!(15, error.column());
+
+    let error = parse_javascript_html("_text[a]='b';", "_text").unwrap_err();
+    assert_eq!(&ParseErrorKind::ExpectedString, error.kind());
+
+    let error = parse_javascript_html("_text['a']=alert(1);", "_text").unwrap_err();
+    assert_eq!(&ParseErrorKind::InvalidSyntax, error.kind());
+
+    assert_eq!("unterminated string at line 1, column 12", {
+        parse_javascript_html("_text['a']='b", "_text").unwrap_err().to_string()
+    });
+}
+
+/// Strings without backslashes, which start escape sequences in the values of maps.
+fn text() -> impl Strategy<Value = String> {
+    "[^\\\\]*"
+}
+
+proptest! {
+    #[test]
+    fn round_trip_b_tree_map(map in prop::collection::btree_map(text(), text(), 0..8)) {
+        let entries = parse_javascript_html(&map.to_javascript_html("_text"), "_text").unwrap();
+
+        let expected: Vec<(String, JsValue)> = map
+            .into_iter()
+            .map(|(key, value)| (key, JsValue::String(value.into())))
+            .collect();
+
+        prop_assert_eq!(expected, entries);
+    }
+
+    #[test]
+    fn parse_arbitrary_map(map in prop::collection::btree_map(any::<String>(), any::<String>(), 0..8)) {
+        // a backslash in a value can start an invalid escape sequence, which is a syntax error in JavaScript
+        match parse_javascript_html(&map.to_javascript_html("_text"), "_text") {
+            Ok(entries) => prop_assert_eq!(map.len(), entries.len()),
+            Err(error) => prop_assert!(matches!(
+                error.kind(),
+                ParseErrorKind::InvalidEscape | ParseErrorKind::InvalidUnicodeEscape
+            )),
+        }
+    }
+}
+
+#[cfg(feature = "serde_json")]
+mod serde_json_map {
+    use map_to_javascript_html::{parse_javascript_html, JavaScriptEntries, MapToJavaScriptHTML};
+    use proptest::prelude::*;
+    use serde_json::{Map, Value};
+
+    use super::text;
+
+    fn value() -> impl Strategy<Value = Value> {
+        let leaf = prop_oneof![
+            Just(Value::Null),
+            any::<bool>().prop_map(Value::from),
+            any::<i64>().prop_map(Value::from),
+            any::<f64>().prop_map(Value::from),
+            text().prop_map(Value::from),
+        ];
+
+        leaf.prop_recursive(3, 24, 4, |inner| {
+            prop_oneof![
+                prop::collection::vec(inner.clone(), 0..4).prop_map(Value::from),
+                prop::collection::btree_map(any::<String>(), inner, 0..4)
+                    .prop_map(|map| Value::Object(map.into_iter().collect())),
+            ]
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn round_trip_serde_json_map(entries in prop::collection::btree_map(text(), value(), 0..8)) {
+            let map: Map<String, Value> = entries.into_iter().collect();
+
+            let entries = parse_javascript_html(&map.to_javascript_html("_text"), "_text").unwrap();
+
+            let expected: Vec<_> = map
+                .javascript_entries()
+                .map(|(key, value)| (key.into_owned(), value.into_owned()))
+                .collect();
+
+            prop_assert_eq!(expected, entries);
+        }
+    }
+}
+
+#[test]
+fn to_javascript_html() {
+    let entries = parse_javascript_html("_text['hello']='It\\'s <\\/script>';", "_text").unwrap();
+
+    let map: BTreeMap<String, String> = entries
+        .into_iter()
+        .map(|(key, value)| match value {
+            JsValue::String(s) => (key, s.into_owned()),
+            value => panic!("{:?}", value),
+        })
+        .collect();
+
+    assert_eq!("_text['hello']='It\\'s <\\/script>';", map.to_javascript_html("_text"));
+}
@@ -0,0 +1,88 @@
+use std::collections::BTreeMap;
+
+use map_to_javascript_html::{
+    ConflictPolicy, JavaScriptEntries, Layered, MapToJavaScriptHTML, MergeError, Renderer,
+};
+
+fn layers() -> (BTreeMap<&'static str, &'static str>, BTreeMap<&'static str, &'static str>) {
+    let mut global = BTreeMap::new();
+    global.insert("title", "My Website");
+    global.insert("login", "Log in");
+
+    let mut page = BTreeMap::new();
+    page.insert("title", "Settings");
+    page.insert("save", "Save");
+
+    (global, page)
+}
+
+#[test]
+fn policies() {
+    let (global, page) = layers();
+
+    let mut layered = Layered::new(ConflictPolicy::LastWins);
+
+    layered.push(&global);
+    layered.push(&page);
+
+    assert_eq!(2, layered.len());
+    assert_eq!(
+        "_text['login']='Log in';_text['save']='Save';_text['title']='Settings';",
+        layered.merge().unwrap().to_javascript_html("_text")
+    );
+
+    let mut layered = Layered::new(ConflictPolicy::FirstWins);
+
+    layered.push(&global);
+    layered.push(&page);
+
+    assert_eq!(
+        "_text['login']='Log in';_text['save']='Save';_text['title']='My Website';",
+        layered.merge().unwrap().to_javascript_html("_text")
+    );
+
+    let mut layered = Layered::new(ConflictPolicy::Error);
+
+    layered.push(&global);
+    layered.push(&page);
+
+    assert_eq!(
+        MergeError::DuplicateKey {
+            key:         String::from("title"),
+            first_layer: 0,
+            layer:       1,
+        },
+        layered.merge().unwrap_err()
+    );
+
+    assert!(Layered::new(ConflictPolicy::default()).merge().unwrap().is_empty());
+}
+
+#[test]
+fn prefix() {
+    let (global, page) = layers();
+
+    let mut layered = Layered::new(ConflictPolicy::Error);
+
+    layered.push(&global);
+    layered.push_with_prefix("settings.", &page);
+
+    let merged = layered.merge().unwrap();
+
+    assert_eq!(4, merged.len());
+    assert_eq!(
+        "_text['login']='Log \
+         in';_text['settings.save']='Save';_text['settings.title']='Settings';_text['title']='My \
+         Website';",
+        merged.to_javascript_html("_text")
+    );
+    assert_eq!(
+        "_text['settings.title']='Settings';_text['missing']=undefined;",
+        merged.to_javascript_html_with_keys("_text", &["settings.title", "missing"])
+    );
+    assert_eq!(
+        merged.to_javascript_html("_text"),
+        Renderer::new().render(&merged, "_text").unwrap()
+    );
+    assert!(merged.javascript_entry("save").is_none());
+}
@@ -0,0 +1,103 @@
+use std::collections::BTreeMap;
+
+use map_to_javascript_html::{
+    negotiate_locale, parse_accept_language, Catalog, LanguageRange, MapToJavaScriptHTML,
+};
+
+#[test]
+fn parse() {
+    assert_eq!(
+        vec![
+            LanguageRange {
+                tag: "fr-CA", quality: 1.0
+            },
+            LanguageRange {
+                tag: "de", quality: 1.0
+            },
+            LanguageRange {
+                tag: "fr", quality: 0.9
+            },
+            LanguageRange {
+                tag: "en", quality: 0.8
+            },
+            LanguageRange {
+                tag: "*", quality: 0.1
+            },
+        ],
+        parse_accept_language("fr-CA, fr;q=0.9, en;q=0.8, *;q=0.1, de")
+    );
+
+    assert_eq!(
+        vec![LanguageRange {
+            tag: "zh-Hant-TW", quality: 0.5
+        }],
+        parse_accept_language(
+            "zh-Hant-TW;q=0.5, ja;q=0, ko;q=1.5, es;q=0.1234, en_US, 123, toolongsubtag, ,"
+        )
+    );
+
+    assert!(parse_accept_language("").is_empty());
+}
+
+#[test]
+fn negotiate() {
+    let available = ["en", "en-GB", "fr", "pt-BR", "zh-Hant"];
+
+    let negotiation = negotiate_locale("fr-CA, en;q=0.5", &available, "en");
+    assert_eq!("fr", negotiation.locale);
+    assert_eq!(vec!["fr", "en"], negotiation.fallback_chain);
+
+    let negotiation = negotiate_locale("EN-gb", &available, "en");
+    assert_eq!("en-GB", negotiation.locale);
+    assert_eq!(vec!["en-GB", "en"], negotiation.fallback_chain);
+
+    let negotiation = negotiate_locale("pt, en;q=0.9", &available, "en");
+    assert_eq!("pt-BR", negotiation.locale);
+    assert_eq!(vec!["pt-BR", "en"], negotiation.fallback_chain);
+
+    let negotiation = negotiate_locale("zh-Hant-HK", &available, "en");
+    assert_eq!("zh-Hant", negotiation.locale);
+
+    let negotiation = negotiate_locale("de;q=0.9, ja", &available, "en");
+    assert_eq!("en", negotiation.locale);
+    assert_eq!(vec!["en"], negotiation.fallback_chain);
+
+    let negotiation = negotiate_locale("*, fr;q=0.5", &available, "en");
+    assert_eq!("en", negotiation.locale);
+
+    let negotiation = negotiate_locale("fr", &["de"], "en");
+    assert_eq!("en", negotiation.locale);
+    assert!(negotiation.fallback_chain.is_empty());
+}
+
+#[test]
+fn catalog_negotiate() {
+    let mut en = BTreeMap::new();
+    en.insert("hello", "Hello!");
+    en.insert("bye", "Bye!");
+
+    let mut fr = BTreeMap::new();
+    fr.insert("hello", "Bonjour !");
+
+    let mut catalog = Catalog::new("en");
+
+    catalog.insert_locale("en", &en);
+    catalog.insert_locale("fr", &fr);
+
+    let localized = catalog.negotiate("fr-FR,fr;q=0.9,en-US;q=0.8");
+
+    assert_eq!("fr", localized.locale());
+    assert_eq!(vec!["fr", "en"], localized.fallback_chain());
+    assert_eq!(
+        "_text['bye']='Bye!';_text['hello']='Bonjour !';",
+        localized.to_javascript_html("_text")
+    );
+
+    let localized = catalog.negotiate("ja");
+
+    assert_eq!("en", localized.locale());
+    assert_eq!(
+        "_text['bye']='Bye!';_text['hello']='Hello!';",
+        localized.to_javascript_html("_text")
+    );
+}
@@ -0,0 +1,152 @@
+use map_to_javascript_html::{
+    MapToJavaScriptHTML, PluralCategory, PluralMessages, PluralRules, Renderer,
+};
+use PluralCategory::*;
+
+fn assert_categories(locale: &str, cases: &[(f64, PluralCategory)]) {
+    let rules = PluralRules::for_locale(locale);
+
+    for (n, category) in cases {
+        assert_eq!(*category, rules.select(*n), "{} {}", locale, n);
+    }
+}
+
+#[test]
+fn select_en() {
+    assert_categories("en", &[(0.0, Other), (1.0, One), (2.0, Other), (1.5, Other), (-1.0, One)]);
+    assert_categories("en-GB", &[(1.0, One), (21.0, Other)]);
+
+    assert_eq!(vec![One, Other], PluralRules::for_locale("en").categories());
+}
+
+#[test]
+fn select_fr() {
+    assert_categories("fr", &[
+        (0.0, One),
+        (1.0, One),
+        (1.5, One),
+        (2.0, Other),
+        (1000000.0, Many),
+        (2000000.0, Many),
+        (1000001.0, Other),
+    ]);
+
+    assert_categories("fr_CA", &[(0.0, One), (2.0, Other)]);
+
+    assert_eq!(vec![One, Many, Other], PluralRules::for_locale("fr").categories());
+}
+
+#[test]
+fn select_ru() {
+    assert_categories("ru", &[
+        (1.0, One),
+        (21.0, One),
+        (101.0, One),
+        (2.0, Few),
+        (4.0, Few),
+        (22.0, Few),
+        (0.0, Many),
+        (5.0, Many),
+        (11.0, Many),
+        (12.0, Many),
+        (111.0, Many),
+        (1.5, Other),
+    ]);
+}
+
+#[test]
+fn select_ar() {
+    assert_categories("ar", &[
+        (0.0, Zero),
+        (1.0, One),
+        (2.0, Two),
+        (3.0, Few),
+        (10.0, Few),
+        (103.0, Few),
+        (11.0, Many),
+        (99.0, Many),
+        (100.0, Other),
+        (102.0, Other),
+        (0.5, Other),
+    ]);
+
+    assert_eq!(
+        vec![Zero, One, Two, Few, Many, Other],
+        PluralRules::for_locale("ar-EG").categories()
+    );
+}
+
+#[test]
+fn select_ja() {
+    assert_categories("ja", &[(0.0, Other), (1.0, Other), (2.0, Other)]);
+
+    assert_eq!("function(){return 'other';}", PluralRules::for_locale("ja").to_javascript());
+    assert_eq!("function(){return 'other';}", PluralRules::for_locale("x-unknown").to_javascript());
+}
+
+#[test]
+fn to_javascript() {
+    assert_eq!(
+        "function(n){n=Math.abs(n);var \
+         s=String(n).split('.')[1]||'',i=Math.floor(n),v=s.length;return \
+         i===1&&v===0?'one':'other';}",
+        PluralRules::for_locale("en").to_javascript()
+    );
+
+    assert_eq!(
+        "function(n){n=Math.abs(n);return \
+         n===0?'zero':n===1?'one':n===2?'two':n%100>=3&&n%100<=10&&n%100%1===0?'few':n%100>=11&&n%\
+         100<=99&&n%100%1===0?'many':'other';}",
+        PluralRules::for_locale("ar").to_javascript()
+    );
+
+    assert_eq!(
+        "function(n){n=Math.abs(n);var \
+         s=String(n).split('.')[1]||'',i=Math.floor(n),v=s.length;return \
+         v===0&&i%10===1&&i%100!==11?'one':v===0&&i%10>=2&&i%10<=4&&!(i%100>=12&&i%100<=14)?'few':\
+         v===0&&i%10===0||v===0&&i%10>=5&&i%10<=9||v===0&&i%100>=11&&i%100<=14?'many':'other';}",
+        PluralRules::for_locale("ru").to_javascript()
+    );
+}
+
+#[test]
+fn plural_messages() {
+    let mut messages = PluralMessages::new("ja");
+
+    messages.insert("items", [(Other, "#個のアイテム")]);
+    messages.insert("quote", [(Other, "It's </script>")]);
+
+    assert_eq!(
+        r"_text['items']=function(n){var v={other:'#個のアイテム'};return (v[(function(){return 'other';})(n)]||v.other||'').split('#').join(n);};_text['quote']=function(n){var v={other:'It\'s <\/script>'};return (v[(function(){return 'other';})(n)]||v.other||'').split('#').join(n);};",
+        messages.to_javascript_html("_text")
+    );
+
+    assert_eq!(
+        "_text['missing']=undefined;",
+        messages.to_javascript_html_with_keys("_text", &["missing"])
+    );
+
+    assert_eq!(
+        messages.to_javascript_html("_text"),
+        Renderer::new().render(&messages, "_text").unwrap()
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn plural_messages_to_writer() {
+    let mut messages = PluralMessages::new("en");
+
+    messages.insert("items", [(One, "1 item"), (Other, "# items")]);
+
+    let mut s = String::new();
+
+    messages.to_javascript_html_to_writer("_text", unsafe { s.as_mut_vec() }).unwrap();
+    assert_eq!(messages.to_javascript_html("_text"), s);
+
+    s.clear();
+    messages
+        .to_javascript_html_with_keys_to_writer("_text", &["items"], unsafe { s.as_mut_vec() })
+        .unwrap();
+    assert_eq!(messages.to_javascript_html("_text"), s);
+}
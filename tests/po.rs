@@ -0,0 +1,115 @@
+use map_to_javascript_html::{parse_po, MapToJavaScriptHTML, ParseErrorKind};
+
+#[test]
+fn parse() {
+    let map = parse_po(
+        r#"# Translation of the website.
+msgid ""
+msgstr ""
+"Content-Type: text/plain; charset=UTF-8\n"
+"Plural-Forms: nplurals=2; plural=(n > 1);\n"
+
+#: src/main.rs:1
+msgid "hello"
+msgstr "Bonjour"
+
+msgid ""
+"multi "
+"line"
+msgstr ""
+"plusieurs "
+"lignes"
+
+#, fuzzy
+msgid "fuzzy"
+msgstr "flou"
+
+msgid "untranslated"
+msgstr ""
+
+#~ msgid "obsolete"
+#~ msgstr "obsolète"
+"#,
+    )
+    .unwrap();
+
+    assert_eq!(2, map.len());
+    assert_eq!("Bonjour", map["hello"]);
+    assert_eq!("plusieurs lignes", map["multi line"]);
+}
+
+#[test]
+fn parse_context_and_plurals() {
+    let map = parse_po(
+        r#"msgctxt "menu"
+msgid "Open"
+msgstr "Ouvrir"
+
+msgid "Open"
+msgstr "Ouvert"
+msgctxt "cart"
+msgid "item"
+msgid_plural "items"
+msgstr[0] "article"
+msgstr[1] "articles"
+"#,
+    )
+    .unwrap();
+
+    assert_eq!(4, map.len());
+    assert_eq!("Ouvrir", map["menu\u{4}Open"]);
+    assert_eq!("Ouvert", map["Open"]);
+    assert_eq!("article", map["cart\u{4}item[0]"]);
+    assert_eq!("articles", map["cart\u{4}item[1]"]);
+}
+
+#[test]
+fn parse_escapes() {
+    let map = parse_po("msgid \"a\\tb\"\nmsgstr \"\\\"quoted\\\"\\n\\\\\"").unwrap();
+
+    assert_eq!("\"quoted\"\n\\", map["a\tb"]);
+}
+
+#[test]
+fn parse_errors() {
+    let error = parse_po("msgid \"a\"\nmsgstr \"b").unwrap_err();
+    assert_eq!(&ParseErrorKind::UnterminatedString, error.kind());
+    assert_eq!((2, 8), (error.line(), error.column()));
+
+    let error = parse_po("msgid \"a\"\nmsgstr \"\\z\"").unwrap_err();
+    assert_eq!(&ParseErrorKind::InvalidEscape, error.kind());
+    assert_eq!((2, 9), (error.line(), error.column()));
+
+    let error = parse_po("msgid \"a\"\n  msgstr[0] \"b\"").unwrap_err();
+    assert_eq!(&ParseErrorKind::UnexpectedKeyword, error.kind());
+    assert_eq!((2, 3), (error.line(), error.column()));
+
+    let error = parse_po("msgid \"a\"\nmsgid_plural \"b\"\nmsgstr[x] \"c\"").unwrap_err();
+    assert_eq!(&ParseErrorKind::InvalidPluralIndex, error.kind());
+    assert_eq!((3, 8), (error.line(), error.column()));
+
+    let error = parse_po("msgstr \"a\"").unwrap_err();
+    assert_eq!(&ParseErrorKind::MissingMsgid, error.kind());
+
+    let error = parse_po("\n\nmsgid \"a\"\n").unwrap_err();
+    assert_eq!(&ParseErrorKind::MissingMsgstr, error.kind());
+    assert_eq!((3, 1), (error.line(), error.column()));
+
+    let error = parse_po("msgid \"a\" x\nmsgstr \"b\"").unwrap_err();
+    assert_eq!(&ParseErrorKind::TrailingCharacters, error.kind());
+    assert_eq!((1, 11), (error.line(), error.column()));
+
+    let error = parse_po("\"a\"").unwrap_err();
+    assert_eq!(&ParseErrorKind::UnexpectedString, error.kind());
+}
+
+#[test]
+fn to_javascript_html() {
+    let map = parse_po("msgid \"hello\"\nmsgstr \"It's </script>\"").unwrap();
+
+    assert_eq!(r"text['hello']='It\'s <\/script>';", map.to_javascript_html("text"));
+    assert_eq!(
+        r"text['hello']='It\'s <\/script>';",
+        map.to_javascript_html_with_keys("text", &["hello"])
+    );
+}
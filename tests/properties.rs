@@ -0,0 +1,70 @@
+use map_to_javascript_html::{parse_properties, MapToJavaScriptHTML, ParseErrorKind};
+
+#[test]
+fn parse() {
+    let map = parse_properties(
+        "# comment\n! another comment\n\nkey1=value1\nkey2 : value2\n  key3 value3\nkey4\n",
+    )
+    .unwrap();
+
+    assert_eq!(4, map.len());
+    assert_eq!("value1", map["key1"]);
+    assert_eq!("value2", map["key2"]);
+    assert_eq!("value3", map["key3"]);
+    assert_eq!("", map["key4"]);
+}
+
+#[test]
+fn parse_escapes() {
+    let map = parse_properties(
+        "a\\ b\\=c=d\\te\\nf\nchinese=\\u4E2D\\u6587\nemoji=\\uD83D\\uDE00\nquote=it\\'s\n",
+    )
+    .unwrap();
+
+    assert_eq!("d\te\nf", map["a b=c"]);
+    assert_eq!("中文", map["chinese"]);
+    assert_eq!("😀", map["emoji"]);
+    assert_eq!("it's", map["quote"]);
+}
+
+#[test]
+fn parse_line_continuations() {
+    let map = parse_properties(
+        "fruits = apple, banana, \\\n         pear, \\\r\n    cantaloupe\r\nbackslash=ends with \
+         \\\\\nnext=1",
+    )
+    .unwrap();
+
+    assert_eq!("apple, banana, pear, cantaloupe", map["fruits"]);
+    assert_eq!("ends with \\", map["backslash"]);
+    assert_eq!("1", map["next"]);
+}
+
+#[test]
+fn parse_errors() {
+    let error = parse_properties("a=1\nb=\\u12G4").unwrap_err();
+    assert_eq!(&ParseErrorKind::InvalidUnicodeEscape, error.kind());
+    assert_eq!(2, error.line());
+    assert_eq!(3, error.column());
+
+    let error = parse_properties("a=x\\\n   y\\uD800").unwrap_err();
+    assert_eq!(&ParseErrorKind::InvalidUnicodeEscape, error.kind());
+    assert_eq!(2, error.line());
+    assert_eq!(5, error.column());
+
+    assert_eq!("invalid unicode escape at line 2, column 5", error.to_string());
+}
+
+#[test]
+fn to_javascript_html() {
+    let map = parse_properties("hello=Hello world!\nwelcome=It's </script>").unwrap();
+
+    assert_eq!(
+        r"text['hello']='Hello world!';text['welcome']='It\'s <\/script>';",
+        map.to_javascript_html("text")
+    );
+    assert_eq!(
+        "text['hello']='Hello world!';text['other']=undefined;",
+        map.to_javascript_html_with_keys("text", &["hello", "other"])
+    );
+}
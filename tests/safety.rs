@@ -0,0 +1,358 @@
+use std::collections::BTreeMap;
+
+use map_to_javascript_html::{
+    parse_javascript_html, verify_script_safe, MapToJavaScriptHTML, PlaceholderSyntax, Redaction,
+    Renderer, UnsafeKeyPolicy, Violation,
+};
+use proptest::{prelude::*, sample::select};
+
+const FRAGMENTS: &[&str] = &[
+    "'",
+    "\"",
+    "`",
+    "\\",
+    "\\'",
+    "\\\\",
+    "</script>",
+    "</SCRIPT ",
+    "<script",
+    "<Script>",
+    "<!--",
+    "-->",
+    "<",
+    "/",
+    "\n",
+    "\r",
+    "\u{2028}",
+    "\u{2029}",
+    "\0",
+    "\t",
+    "\u{7f}",
+    "\u{85}",
+    "{",
+    "}",
+    "{name}",
+    "%s",
+    "%(a)s",
+    "${",
+    "#",
+    "__proto__",
+    "hello",
+    "1",
+    ";",
+    "中文",
+];
+
+/// Strings which are likely to contain the characters dangerous to JavaScript in HTML.
+fn text() -> impl Strategy<Value = String> {
+    prop::collection::vec(
+        prop_oneof![select(FRAGMENTS).prop_map(String::from), any::<String>()],
+        0..6,
+    )
+    .prop_map(|parts| parts.concat())
+}
+
+fn check(script: &str, statements: Option<usize>) -> Result<(), TestCaseError> {
+    if let Err(violation) = verify_script_safe(script.as_bytes(), "text") {
+        return Err(TestCaseError::fail(format!("{}: {:?}", violation, script)));
+    }
+
+    if let Some(statements) = statements {
+        prop_assert_eq!(
+            statements,
+            parse_javascript_html(script, "text").unwrap().len(),
+            "{:?}",
+            script
+        );
+    }
+
+    Ok(())
+}
+
+fn check_map<M: MapToJavaScriptHTML<String>>(
+    map: &M,
+    len: usize,
+    keys: &[&str],
+) -> Result<(), TestCaseError> {
+    let script = map.to_javascript_html("text");
+
+    check(&script, Some(len))?;
+    check(&map.to_javascript_html_with_keys("text", keys), Some(keys.len()))?;
+
+    #[cfg(feature = "std")]
+    {
+        let mut s = String::new();
+
+        map.to_javascript_html_to_writer("text", unsafe { s.as_mut_vec() }).unwrap();
+
+        prop_assert_eq!(&script, &s);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn verify() {
+    assert_eq!(Ok(()), verify_script_safe(b"", "a"));
+    assert_eq!(Ok(()), verify_script_safe(br#"a['b']={"c":["d",1,null]};"#, "a"));
+    assert_eq!(Ok(()), verify_script_safe(br"a['b']='<\/script><\script><\!--';", "a"));
+    assert_eq!(
+        Ok(()),
+        verify_script_safe(
+            br"a['b']=function(p){p=p||{};return ''+p.name+'\'s '+p[0]+p['a-b'];};",
+            "a"
+        )
+    );
+
+    assert_eq!(
+        Err(Violation::ScriptTag {
+            offset: 7
+        }),
+        verify_script_safe(b"a['b']=</ScRiPt>", "a")
+    );
+    assert_eq!(
+        Err(Violation::ScriptTag {
+            offset: 8
+        }),
+        verify_script_safe(b"a['b']='<script>';", "a")
+    );
+    assert_eq!(
+        Err(Violation::CommentOpen {
+            offset: 8
+        }),
+        verify_script_safe(b"a['b']='<!--';", "a")
+    );
+
+    for (script, offset) in [
+        (&br"a['b']='c\';"[..], 0),
+        (br"a['b']='c\", 0),
+        (b"a['b']='c\nd';", 0),
+        (b"a['b']='c'alert(1);", 0),
+        (br#"a['b']=""'';"#, 0),
+        (b"a['b']='c';b['c']='d';", 11),
+        (b"a['b']='x';alert(1);'';", 11),
+        (b"a['b']='x'+alert(1)+'';", 0),
+        (b"a['b']=function(){return 'x'+alert(1)+'';};", 0),
+        (b"a['b']=function(p){return p.a();};", 0),
+        (b"a['b']=function(p){alert(1);return '';};", 0),
+        (b"a['b']='c';\xff", 11),
+    ] {
+        assert_eq!(
+            Err(Violation::InvalidStatement {
+                offset
+            }),
+            verify_script_safe(script, "a"),
+            "{:?}",
+            String::from_utf8_lossy(script)
+        );
+    }
+}
+
+#[test]
+fn escape_breakouts() {
+    let mut map = BTreeMap::new();
+    map.insert("a\\", "b\\");
+    map.insert("c", "d\\\\\\");
+    map.insert("e", "line\nbreak\r\\\nend");
+    map.insert("f", "<script><SCRIPT</script ><!--");
+
+    assert_eq!(
+        concat!(
+            r"text['a\\']='b\\';text['c']='d\\\\';text['e']='line\nbreak\r\",
+            "\nend';",
+            r"text['f']='<\script><\SCRIPT<\/script ><\!--';"
+        ),
+        map.to_javascript_html("text")
+    );
+}
+
+proptest! {
+    #[test]
+    fn b_tree_map(map in prop::collection::btree_map(text(), text(), 0..8), extra in text()) {
+        let mut keys: Vec<&str> = map.keys().map(|key| key.as_str()).collect();
+        keys.push(extra.as_str());
+
+        check_map(&map, map.len(), &keys)?;
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn hash_map(map in prop::collection::hash_map(text(), text(), 0..8), extra in text()) {
+        let mut keys: Vec<&str> = map.keys().map(|key| key.as_str()).collect();
+        keys.push(extra.as_str());
+
+        check_map(&map, map.len(), &keys)?;
+    }
+
+    // the output modes which `verify_script_safe` does not accept are run in Boa by `tests/engine.rs`
+    #[test]
+    fn renderer(
+        map in prop::collection::btree_map(text(), text(), 0..8),
+        syntax in prop_oneof![
+            Just(None),
+            Just(Some(PlaceholderSyntax::Braces)),
+            Just(Some(PlaceholderSyntax::Printf)),
+            Just(Some(PlaceholderSyntax::Delimiters {
+                open: String::from("${"), close: String::from("}")
+            })),
+        ],
+        null_prototype in any::<bool>(),
+        redact in any::<bool>(),
+        ascii_only in any::<bool>(),
+        max_length in prop::option::of(0..8usize),
+    ) {
+        let mut renderer = Renderer::new();
+
+        if let Some(syntax) = syntax {
+            renderer = renderer.placeholders(syntax);
+        }
+
+        if null_prototype {
+            renderer = renderer.unsafe_keys(UnsafeKeyPolicy::NullPrototype);
+        }
+
+        if redact {
+            renderer = renderer.redact(Redaction::sensitive());
+        }
+
+        if ascii_only {
+            renderer = renderer.ascii_only(true);
+        }
+
+        if let Some(max_length) = max_length {
+            renderer = renderer.max_value_length(max_length);
+        }
+
+        let statements = if redact { None } else { Some(map.len()) };
+
+        if let Ok(script) = renderer.render(&map, "text") {
+            check(&script, statements)?;
+
+            if ascii_only {
+                prop_assert!(script.is_ascii(), "{:?}", script);
+            }
+        }
+    }
+}
+
+#[cfg(feature = "serde_json")]
+mod serde_json_map {
+    use proptest::prelude::*;
+    use serde_json::{Map, Value};
+
+    use super::{check_map, text};
+
+    fn value() -> impl Strategy<Value = Value> {
+        let leaf = prop_oneof![
+            Just(Value::Null),
+            any::<bool>().prop_map(Value::from),
+            any::<i64>().prop_map(Value::from),
+            any::<f64>().prop_map(Value::from),
+            text().prop_map(Value::from),
+        ];
+
+        leaf.prop_recursive(3, 24, 4, |inner| {
+            prop_oneof![
+                prop::collection::vec(inner.clone(), 0..4).prop_map(Value::from),
+                prop::collection::btree_map(super::text(), inner, 0..4)
+                    .prop_map(|map| Value::Object(map.into_iter().collect())),
+            ]
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn serde_json_map(entries in prop::collection::btree_map(text(), value(), 0..8), extra in text()) {
+            let map: Map<String, Value> = entries.into_iter().collect();
+
+            let mut keys: Vec<&str> = map.keys().map(|key| key.as_str()).collect();
+            keys.push(extra.as_str());
+
+            check_map(&map, map.len(), &keys)?;
+        }
+    }
+}
+
+#[cfg(feature = "toml")]
+mod toml_table {
+    use proptest::prelude::*;
+    use toml::{Table, Value};
+
+    use super::{check_map, text};
+
+    fn value() -> impl Strategy<Value = Value> {
+        let leaf = prop_oneof![
+            any::<bool>().prop_map(Value::from),
+            any::<i64>().prop_map(Value::from),
+            any::<f64>().prop_map(Value::from),
+            text().prop_map(Value::from),
+        ];
+
+        leaf.prop_recursive(3, 24, 4, |inner| {
+            prop_oneof![
+                prop::collection::vec(inner.clone(), 0..4).prop_map(Value::Array),
+                prop::collection::btree_map(super::text(), inner, 0..4)
+                    .prop_map(|map| Value::Table(map.into_iter().collect())),
+            ]
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn toml_table(entries in prop::collection::btree_map(text(), value(), 0..8), extra in text()) {
+            let map: Table = entries.into_iter().collect();
+
+            let mut keys: Vec<&str> = map.keys().map(|key| key.as_str()).collect();
+            keys.push(extra.as_str());
+
+            check_map(&map, map.len(), &keys)?;
+        }
+    }
+}
+
+#[cfg(feature = "serde_yaml")]
+mod serde_yaml_mapping {
+    use map_to_javascript_html::MapToJavaScriptHTML;
+    use proptest::prelude::*;
+    use serde_yaml::{Mapping, Value};
+
+    use super::{check, text};
+
+    fn value() -> impl Strategy<Value = Value> {
+        let leaf = prop_oneof![
+            Just(Value::Null),
+            any::<bool>().prop_map(Value::from),
+            any::<i64>().prop_map(Value::from),
+            any::<f64>().prop_map(Value::from),
+            text().prop_map(Value::from),
+        ];
+
+        leaf.prop_recursive(3, 24, 4, |inner| {
+            prop_oneof![
+                prop::collection::vec(inner.clone(), 0..4).prop_map(Value::Sequence),
+                prop::collection::vec((inner.clone(), inner), 0..4)
+                    .prop_map(|entries| Value::Mapping(entries.into_iter().collect())),
+            ]
+        })
+    }
+
+    proptest! {
+        #[test]
+        fn serde_yaml_mapping(entries in prop::collection::vec((value(), value()), 0..8), extra in text()) {
+            let map: Mapping = entries.into_iter().collect();
+
+            check(&map.to_javascript_html("text"), Some(map.len()))?;
+
+            let mut keys: Vec<&str> = map.keys().filter_map(Value::as_str).collect();
+            keys.push(extra.as_str());
+
+            check(&map.to_javascript_html_with_keys("text", &keys), Some(keys.len()))?;
+
+            let mut s = String::new();
+
+            map.to_javascript_html_to_writer("text", unsafe { s.as_mut_vec() }).unwrap();
+
+            prop_assert_eq!(map.to_javascript_html("text"), s);
+        }
+    }
+}
@@ -0,0 +1,428 @@
+use std::collections::BTreeMap;
+
+use map_to_javascript_html::{
+    Declaration, Immutability, Indentation, JavaScriptEntries, PlaceholderSyntax, RenderError,
+    Renderer, ScriptBuilder, SizeLimitPolicy, UnsafeKeyPolicy,
+};
+
+#[test]
+fn build() {
+    let mut text = BTreeMap::new();
+    text.insert("hello", "Hello {name}!");
+
+    let mut routes = BTreeMap::new();
+    routes.insert("home", "/");
+
+    assert_eq!(
+        "_text['hello']='Hello {name}!';_routes['home']='/';",
+        ScriptBuilder::new().variable("_text", &text).variable("_routes", &routes).build().unwrap()
+    );
+
+    let maps: [(&str, &dyn JavaScriptEntries); 2] = [("_text", &text), ("_routes", &routes)];
+
+    let builder = maps
+        .iter()
+        .fold(ScriptBuilder::new(), |builder, (name, map)| builder.variable(name, *map))
+        .declaration(Declaration::Const)
+        .iife(true)
+        .renderer(Renderer::new().placeholders(PlaceholderSyntax::Braces));
+
+    let expected = "(function(){const _text={};_text['hello']=function(p){return 'Hello \
+                    '+p.name+'!';};const _routes={};_routes['home']='/';})();";
+
+    assert_eq!(expected, builder.build().unwrap());
+
+    let mut s = String::from("<script>");
+    assert_eq!(expected, builder.build_to_string(&mut s).unwrap());
+    assert_eq!(format!("<script>{}", expected), s);
+
+    #[cfg(feature = "std")]
+    {
+        let mut output = Vec::new();
+        builder.build_to_writer(&mut output).unwrap();
+        assert_eq!(expected.as_bytes(), output.as_slice());
+    }
+
+    assert_eq!("", ScriptBuilder::new().build().unwrap());
+    assert_eq!("(function(){})();", ScriptBuilder::new().iife(true).build().unwrap());
+}
+
+#[test]
+fn error() {
+    let mut text = BTreeMap::new();
+    text.insert("hello", "Hello!");
+
+    let mut broken = BTreeMap::new();
+    broken.insert("hello", "Hello {my name}!");
+
+    let builder = ScriptBuilder::new()
+        .renderer(Renderer::new().placeholders(PlaceholderSyntax::Braces))
+        .declaration(Declaration::Var)
+        .variable("_text", &text)
+        .variable("_broken", &broken);
+
+    let mut s = String::from("<script>");
+
+    assert!(matches!(builder.build_to_string(&mut s), Err(RenderError::InvalidPlaceholder { .. })));
+    assert_eq!("<script>", s);
+}
+
+#[test]
+fn namespaces() {
+    let mut text = BTreeMap::new();
+    text.insert("hello", "Hello!");
+
+    let builder = ScriptBuilder::new()
+        .namespaces(true)
+        .declaration(Declaration::Var)
+        .variable("window.App.i18n.messages", &text)
+        .variable("_text", &text);
+
+    assert_eq!(
+        "window.App=window.App||{};window.App.i18n=window.App.i18n||{};window.App.i18n.\
+         messages=window.App.i18n.messages||{};window.App.i18n.messages['hello']='Hello!';var \
+         _text={};_text['hello']='Hello!';",
+        builder.build().unwrap()
+    );
+
+    assert_eq!(
+        "window.App=window.App||{};window.App['hello']='Hello!';",
+        ScriptBuilder::new().namespaces(true).variable("window.App", &text).build().unwrap()
+    );
+
+    for path in ["window..App", "window.App-1", "window.", "1st", "window['App']"] {
+        let mut s = String::new();
+
+        assert!(matches!(
+            ScriptBuilder::new()
+                .namespaces(true)
+                .variable("window.App", &text)
+                .variable(path, &text)
+                .build_to_string(&mut s),
+            Err(RenderError::InvalidVariablePath { .. })
+        ));
+        assert!(s.is_empty());
+    }
+}
+
+#[test]
+fn merge() {
+    let mut text = BTreeMap::new();
+    text.insert("hello", "Hello {name}!");
+    text.insert("bye", "Bye!");
+
+    let builder = ScriptBuilder::new().variable("_text", &text);
+
+    assert_eq!(
+        "_text={'bye':'Bye!','hello':'Hello {name}!'};",
+        builder.clone().object_literal(true).build().unwrap()
+    );
+    assert_eq!(
+        "let _text={'bye':'Bye!','hello':function(p){return 'Hello '+p.name+'!';}};",
+        builder
+            .clone()
+            .object_literal(true)
+            .declaration(Declaration::Let)
+            .renderer(Renderer::new().placeholders(PlaceholderSyntax::Braces))
+            .build()
+            .unwrap()
+    );
+    assert_eq!(
+        "var _text=Object.assign(_text||{},{'bye':'Bye!','hello':'Hello {name}!'});",
+        builder
+            .clone()
+            .object_literal(true)
+            .merge(true)
+            .declaration(Declaration::Const)
+            .build()
+            .unwrap()
+    );
+    assert_eq!(
+        "var _text=_text||{};_text['bye']='Bye!';_text['hello']='Hello {name}!';",
+        builder.clone().merge(true).build().unwrap()
+    );
+
+    let builder = ScriptBuilder::new().variable("window.App.text", &text).namespaces(true);
+
+    assert_eq!(
+        "window.App=window.App||{};window.App.text=Object.assign(window.App.text||{},{'bye':'Bye!'\
+         ,'hello':'Hello {name}!'});",
+        builder.clone().object_literal(true).merge(true).build().unwrap()
+    );
+    assert_eq!(
+        "window.App=window.App||{};window.App.text={'bye':'Bye!','hello':'Hello {name}!'};",
+        builder.clone().object_literal(true).build().unwrap()
+    );
+    assert_eq!(
+        "window.App=window.App||{};window.App.text=window.App.text||{};window.App.text['bye']='\
+         Bye!';window.App.text['hello']='Hello {name}!';",
+        builder.merge(true).build().unwrap()
+    );
+
+    assert_eq!(
+        "window._text=window._text||{};window._text['bye']='Bye!';window._text['hello']='Hello \
+         {name}!';",
+        ScriptBuilder::new().variable("window._text", &text).merge(true).build().unwrap()
+    );
+}
+
+#[test]
+fn immutable() {
+    let mut text = BTreeMap::new();
+    text.insert("hello", "Hello!");
+
+    let freeze = ScriptBuilder::new()
+        .renderer(Renderer::new().immutable(Immutability::Freeze))
+        .variable("_text", &text);
+
+    assert_eq!(
+        "_text=Object.freeze({'hello':'Hello!'});",
+        freeze.clone().object_literal(true).build().unwrap()
+    );
+    assert_eq!(
+        "var _text=Object.freeze(Object.assign({},_text,{'hello':'Hello!'}));",
+        freeze.clone().object_literal(true).merge(true).build().unwrap()
+    );
+    assert_eq!(
+        "var _text=Object.assign({},_text);_text['hello']='Hello!';Object.freeze(_text);",
+        freeze.clone().merge(true).build().unwrap()
+    );
+    assert_eq!(
+        "const _text={};_text['hello']='Hello!';Object.freeze(_text);",
+        freeze.declaration(Declaration::Const).build().unwrap()
+    );
+
+    let define = ScriptBuilder::new()
+        .renderer(Renderer::new().immutable(Immutability::DefineProperty))
+        .variable("_text", &text);
+
+    assert_eq!(
+        "_text=Object.defineProperties({},{'hello':{value:'Hello!',enumerable:true}});",
+        define.clone().object_literal(true).build().unwrap()
+    );
+    assert!(matches!(
+        define.clone().object_literal(true).merge(true).build(),
+        Err(RenderError::ReadOnlyMerge)
+    ));
+
+    let mut output = String::from("<script>");
+
+    assert!(matches!(
+        define.merge(true).build_to_string(&mut output),
+        Err(RenderError::ReadOnlyMerge)
+    ));
+    assert_eq!("<script>", output);
+
+    assert_eq!(
+        "window.App=window.App||{};window.App.text=Object.assign({},window.App.text);window.App.\
+         text['hello']='Hello!';Object.freeze(window.App.text);",
+        ScriptBuilder::new()
+            .renderer(Renderer::new().immutable(Immutability::Freeze))
+            .namespaces(true)
+            .variable("window.App.text", &text)
+            .build()
+            .unwrap()
+    );
+}
+
+#[test]
+fn null_prototype() {
+    let mut text = BTreeMap::new();
+    text.insert("__proto__", "Hello!");
+
+    let builder = ScriptBuilder::new()
+        .renderer(Renderer::new().unsafe_keys(UnsafeKeyPolicy::NullPrototype))
+        .variable("_text", &text);
+
+    assert_eq!(
+        "var _text=Object.create(null);_text['__proto__']='Hello!';",
+        builder.clone().declaration(Declaration::Var).build().unwrap()
+    );
+
+    // an existing object which is merged into may have a prototype
+    assert!(matches!(builder.clone().merge(true).build(), Err(RenderError::UnsafeKey { .. })));
+    assert!(matches!(
+        builder.clone().object_literal(true).merge(true).build(),
+        Err(RenderError::UnsafeKey { .. })
+    ));
+    assert!(matches!(
+        ScriptBuilder::new()
+            .renderer(Renderer::new().unsafe_keys(UnsafeKeyPolicy::NullPrototype))
+            .namespaces(true)
+            .variable("window.App.text", &text)
+            .build(),
+        Err(RenderError::UnsafeKey { .. })
+    ));
+
+    // the existing object is copied to a new object to be frozen
+    assert_eq!(
+        "var _text=Object.assign(Object.create(null),_text);_text['__proto__']='Hello!';Object.\
+         freeze(_text);",
+        builder
+            .clone()
+            .merge(true)
+            .renderer(
+                Renderer::new()
+                    .unsafe_keys(UnsafeKeyPolicy::NullPrototype)
+                    .immutable(Immutability::Freeze)
+            )
+            .build()
+            .unwrap()
+    );
+    assert_eq!(
+        "_text=Object.assign(Object.create(null),{['__proto__']:'Hello!'});",
+        builder.clone().object_literal(true).build().unwrap()
+    );
+    assert_eq!(
+        "var _text=Object.freeze(Object.assign(Object.create(null),_text,{['__proto__']:'Hello!'\
+         }));",
+        builder
+            .clone()
+            .object_literal(true)
+            .merge(true)
+            .renderer(
+                Renderer::new()
+                    .unsafe_keys(UnsafeKeyPolicy::NullPrototype)
+                    .immutable(Immutability::Freeze)
+            )
+            .build()
+            .unwrap()
+    );
+    assert_eq!(
+        "_text=Object.defineProperties(Object.create(null),{['__proto__']:{value:'Hello!',\
+         enumerable:true}});",
+        builder
+            .object_literal(true)
+            .renderer(
+                Renderer::new()
+                    .unsafe_keys(UnsafeKeyPolicy::NullPrototype)
+                    .immutable(Immutability::DefineProperty)
+            )
+            .build()
+            .unwrap()
+    );
+
+    // the variable is neither declared nor initialized, so its object may have a prototype
+    assert!(matches!(
+        ScriptBuilder::new()
+            .renderer(Renderer::new().unsafe_keys(UnsafeKeyPolicy::NullPrototype))
+            .variable("_text", &text)
+            .build(),
+        Err(RenderError::UnsafeKey { .. })
+    ));
+    assert!(matches!(
+        ScriptBuilder::new()
+            .renderer(Renderer::new().unsafe_keys(UnsafeKeyPolicy::Skip))
+            .namespaces(true)
+            .variable("window.__proto__.text", &text)
+            .build(),
+        Err(RenderError::InvalidVariablePath { .. })
+    ));
+}
+
+#[test]
+fn ascii_only() {
+    let mut text = BTreeMap::new();
+    text.insert("中", "文");
+
+    assert_eq!(
+        r"const _text={'\u4e2d':'\u6587'};",
+        ScriptBuilder::new()
+            .declaration(Declaration::Const)
+            .object_literal(true)
+            .renderer(Renderer::new().ascii_only(true))
+            .variable("_text", &text)
+            .build()
+            .unwrap()
+    );
+}
+
+#[test]
+fn minify() {
+    let mut text = BTreeMap::new();
+    text.insert("hello", "It's");
+    text.insert("1", "a");
+    text.insert("a-b", "b");
+    text.insert("__proto__", "c");
+
+    assert_eq!(
+        r#"const _text=Object.assign(Object.create(null),{1:'a',['__proto__']:'c','a-b':'b',hello:"It's"});"#,
+        ScriptBuilder::new()
+            .declaration(Declaration::Const)
+            .object_literal(true)
+            .renderer(Renderer::new().minify(true).unsafe_keys(UnsafeKeyPolicy::NullPrototype))
+            .variable("_text", &text)
+            .build()
+            .unwrap()
+    );
+}
+
+#[test]
+fn pretty() {
+    let mut text = BTreeMap::new();
+    text.insert("a", "A");
+    text.insert("b", "B");
+
+    let renderer = Renderer::new().pretty(Indentation::Spaces(4));
+
+    assert_eq!(
+        "(function(){\nwindow.App=window.App||{};\nwindow.App.text=window.App.text||{};\nwindow.\
+         App.text['a'] = 'A';\nwindow.App.text['b'] = 'B';\n})();\n",
+        ScriptBuilder::new()
+            .iife(true)
+            .namespaces(true)
+            .renderer(renderer.clone())
+            .variable("window.App.text", &text)
+            .build()
+            .unwrap()
+    );
+
+    assert_eq!(
+        "const _text={\n    'a': 'A',\n    'b': 'B'\n};\nconst _empty={};\n",
+        ScriptBuilder::new()
+            .declaration(Declaration::Const)
+            .object_literal(true)
+            .renderer(renderer)
+            .variable("_text", &text)
+            .variable("_empty", &BTreeMap::<&str, &str>::new())
+            .build()
+            .unwrap()
+    );
+}
+
+#[test]
+fn size_limit() {
+    let mut text = BTreeMap::new();
+    text.insert("hello", "Hello!");
+
+    let mut routes = BTreeMap::new();
+    routes.insert("home", "/");
+
+    let builder = ScriptBuilder::new().variable("_text", &text).variable("_routes", &routes);
+
+    // the limit applies to each variable, not to the whole fragment
+    assert_eq!(
+        "_text['hello']='Hello!';_routes['home']='/';",
+        builder
+            .clone()
+            .renderer(Renderer::new().size_limit(24, SizeLimitPolicy::Reject))
+            .build()
+            .unwrap()
+    );
+    assert!(matches!(
+        builder.clone().renderer(Renderer::new().size_limit(23, SizeLimitPolicy::Reject)).build(),
+        Err(RenderError::SizeLimitExceeded {
+            limit: 23, size: 24
+        })
+    ));
+
+    let mut output = String::from("<script>");
+
+    assert!(matches!(
+        builder
+            .renderer(Renderer::new().size_limit(24, SizeLimitPolicy::Truncate))
+            .build_to_string(&mut output),
+        Err(RenderError::TruncatedScript)
+    ));
+    assert_eq!("<script>", output);
+}
@@ -0,0 +1,91 @@
+#![cfg(feature = "serde_yaml")]
+
+use map_to_javascript_html::MapToJavaScriptHTML;
+use serde_yaml::{Mapping, Value};
+
+#[test]
+fn to_javascript_html() {
+    let mut map = Mapping::new();
+    map.insert(Value::from("test-1"), Value::from("Test 1!"));
+    assert_eq!("text['test-1']='Test 1!';", map.to_javascript_html("text"));
+
+    let mut map = Mapping::new();
+    map.insert(Value::from("test-1'"), Value::from(1));
+    assert_eq!(r"text['test-1\'']=1;", map.to_javascript_html("text"));
+
+    let mut map = Mapping::new();
+    map.insert(Value::from("test-1"), serde_yaml::to_value([1, 2, 3, 4, 5]).unwrap());
+    assert_eq!("text['test-1']=[1,2,3,4,5];", map.to_javascript_html("text"));
+}
+
+#[test]
+fn to_javascript_html_non_string_keys() {
+    let map: Mapping = serde_yaml::from_str(
+        r#"
+        1: one
+        1.5: one and a half
+        .inf: infinity
+        true: yes
+        ~: nothing
+        [a, b]: sequence
+        !tagged key: tagged
+        nested:
+          2: two
+          "</script>": !tagged value
+          list: [.nan, null]
+        "#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        r#"text['1']='one';text['1.5']='one and a half';text['Infinity']='infinity';text['true']='yes';text['null']='nothing';text['["a","b"]']='sequence';text['key']='tagged';text['nested']={"2":"two","<\/script>":"value","list":[NaN,null]};"#,
+        map.to_javascript_html("text")
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn to_javascript_html_to_writer() {
+    let mut s = String::new();
+
+    let mut map = Mapping::new();
+    map.insert(Value::from("test-1"), Value::from("Test 1!"));
+    map.to_javascript_html_to_writer("text", unsafe { s.as_mut_vec() }).unwrap();
+    assert_eq!("text['test-1']='Test 1!';", s);
+
+    let mut map = Mapping::new();
+    map.insert(Value::from(1), Value::from(1));
+    s.clear();
+    map.to_javascript_html_to_writer("text", unsafe { s.as_mut_vec() }).unwrap();
+    assert_eq!("text['1']=1;", s);
+}
+
+#[test]
+fn to_javascript_html_with_keys() {
+    let mut map = Mapping::new();
+    map.insert(Value::from("test-1"), Value::from("Test 1!"));
+    map.insert(Value::from(2), Value::from("Test 2!"));
+
+    assert_eq!("text['test-1']='Test 1!';", map.to_javascript_html_with_keys("text", &["test-1"]));
+    assert_eq!("text['2']='Test 2!';", map.to_javascript_html_with_keys("text", &["2"]));
+    assert_eq!("text['test-3']=undefined;", map.to_javascript_html_with_keys("text", &["test-3"]));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn to_javascript_html_with_keys_to_writer() {
+    let mut map = Mapping::new();
+    map.insert(Value::from("test-1"), Value::from("Test 1!"));
+    map.insert(Value::from("test-2"), Value::from("Test 2!"));
+
+    let mut s = String::new();
+
+    map.to_javascript_html_with_keys_to_writer("text", &["test-1"], unsafe { s.as_mut_vec() })
+        .unwrap();
+    assert_eq!("text['test-1']='Test 1!';", s);
+
+    s.clear();
+    map.to_javascript_html_with_keys_to_writer("text", &["test-3"], unsafe { s.as_mut_vec() })
+        .unwrap();
+    assert_eq!("text['test-3']=undefined;", s);
+}
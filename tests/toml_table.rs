@@ -0,0 +1,92 @@
+#![cfg(feature = "toml")]
+
+use map_to_javascript_html::MapToJavaScriptHTML;
+use toml::{Table, Value};
+
+#[test]
+fn to_javascript_html() {
+    let mut map = Table::new();
+    map.insert("test-1".to_string(), Value::from("Test 1!"));
+    assert_eq!("text['test-1']='Test 1!';", map.to_javascript_html("text"));
+
+    let mut map = Table::new();
+    map.insert("test-1'".to_string(), Value::from(1));
+    assert_eq!(r"text['test-1\'']=1;", map.to_javascript_html("text"));
+
+    let mut map = Table::new();
+    map.insert("test-1".to_string(), Value::from(1.5));
+    map.insert("test-2".to_string(), Value::from(f64::INFINITY));
+    assert_eq!("text['test-1']=1.5;text['test-2']=Infinity;", map.to_javascript_html("text"));
+
+    let mut map = Table::new();
+    map.insert("test-1".to_string(), Value::try_from([1, 2, 3, 4, 5]).unwrap());
+    assert_eq!("text['test-1']=[1,2,3,4,5];", map.to_javascript_html("text"));
+}
+
+#[test]
+fn to_javascript_html_datetime() {
+    let map: Table = r#"
+        date = 1979-05-27
+        time = 07:32:00
+        datetime = 1979-05-27T07:32:00Z
+        nested = { at = 1979-05-27T00:32:00-07:00, name = "</script>" }
+    "#
+    .parse()
+    .unwrap();
+
+    assert_eq!(
+        r#"text['date']='1979-05-27';text['datetime']='1979-05-27T07:32:00Z';text['nested']={"at":"1979-05-27T00:32:00-07:00","name":"<\/script>"};text['time']='07:32:00';"#,
+        map.to_javascript_html("text")
+    );
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn to_javascript_html_to_writer() {
+    let mut s = String::new();
+
+    let mut map = Table::new();
+    map.insert("test-1".to_string(), Value::from("Test 1!"));
+    map.to_javascript_html_to_writer("text", unsafe { s.as_mut_vec() }).unwrap();
+    assert_eq!("text['test-1']='Test 1!';", s);
+
+    map.clear();
+    map.insert("test-1'".to_string(), Value::from(1));
+    s.clear();
+    map.to_javascript_html_to_writer("text", unsafe { s.as_mut_vec() }).unwrap();
+    assert_eq!(r"text['test-1\'']=1;", s);
+
+    let map: Table = "test-1 = [1979-05-27, { a = true }]".parse().unwrap();
+    s.clear();
+    map.to_javascript_html_to_writer("text", unsafe { s.as_mut_vec() }).unwrap();
+    assert_eq!(r#"text['test-1']=["1979-05-27",{"a":true}];"#, s);
+}
+
+#[test]
+fn to_javascript_html_with_keys() {
+    let mut map = Table::new();
+    map.insert(String::from("test-1"), Value::from("Test 1!"));
+    map.insert(String::from("test-2"), Value::from("Test 2!"));
+
+    assert_eq!("text['test-1']='Test 1!';", map.to_javascript_html_with_keys("text", &["test-1"]));
+    assert_eq!("text['test-3']=undefined;", map.to_javascript_html_with_keys("text", &["test-3"]));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn to_javascript_html_with_keys_to_writer() {
+    let mut map = Table::new();
+    map.insert(String::from("test-1"), Value::from("Test 1!"));
+    map.insert(String::from("test-2"), Value::from("Test 2!"));
+
+    let mut s = String::new();
+
+    map.to_javascript_html_with_keys_to_writer("text", &["test-1"], unsafe { s.as_mut_vec() })
+        .unwrap();
+    assert_eq!("text['test-1']='Test 1!';", s);
+
+    s.clear();
+    map.to_javascript_html_with_keys_to_writer("text", &["test-3"], unsafe { s.as_mut_vec() })
+        .unwrap();
+    assert_eq!("text['test-3']=undefined;", s);
+}
@@ -0,0 +1,108 @@
+#![cfg(feature = "watch")]
+
+use std::{
+    ops::Deref,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, Instant},
+};
+
+use map_to_javascript_html::CatalogWatcher;
+
+/// A temporary directory which is removed when dropped, even if the test panics.
+struct TempDir(PathBuf);
+
+impl TempDir {
+    fn new(name: &str) -> TempDir {
+        let dir = std::env::temp_dir().join(format!(
+            "map-to-javascript-html-{}-{}",
+            name,
+            std::process::id()
+        ));
+
+        std::fs::create_dir_all(&dir).unwrap();
+
+        TempDir(dir)
+    }
+}
+
+impl Deref for TempDir {
+    type Target = Path;
+
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.0);
+    }
+}
+
+/// Wait until the output of a locale is `expected`, which happens after the watcher reloads.
+fn wait_for(watcher: &CatalogWatcher, locale: &str, expected: &[u8]) {
+    let start = Instant::now();
+
+    while &*watcher.rendered(locale) != expected && start.elapsed() < Duration::from_secs(10) {
+        thread::sleep(Duration::from_millis(20));
+    }
+
+    assert_eq!(expected, &*watcher.rendered(locale));
+}
+
+#[test]
+fn watch() {
+    let dir = TempDir::new("watch");
+
+    std::fs::write(dir.join("en.properties"), "hello=Hello!\nbye=Bye!\n").unwrap();
+    std::fs::write(dir.join("fr.properties"), "hello=Bonjour !\n").unwrap();
+
+    let watcher = CatalogWatcher::new(&*dir, "en", "_text").unwrap();
+
+    assert_eq!(b"_text['bye']='Bye!';_text['hello']='Bonjour !';", &*watcher.rendered("fr-CA"));
+    assert_eq!(b"_text['bye']='Bye!';_text['hello']='Hello!';", &*watcher.rendered("ja"));
+    assert_eq!(
+        b"_text['bye']='Bye!';_text['hello']='Bonjour !';",
+        &*watcher.negotiate("de, fr;q=0.9")
+    );
+
+    std::fs::write(dir.join("fr.properties"), "hello=Salut !\n").unwrap();
+
+    let expected: &[u8] = b"_text['bye']='Bye!';_text['hello']='Salut !';";
+
+    wait_for(&watcher, "fr", expected);
+    assert_eq!(vec!["en", "fr"], watcher.catalog().locales().collect::<Vec<_>>());
+
+    // the watcher may see the file before it is written, so only the explicit reload is checked
+    std::fs::write(dir.join("de.po"), "msgstr \"\"\n").unwrap();
+
+    assert!(watcher.reload().unwrap_err().to_string().contains("de.po"));
+    assert_eq!(expected, &*watcher.rendered("fr"));
+
+    std::fs::remove_file(dir.join("de.po")).unwrap();
+
+    watcher.reload().unwrap();
+    watcher.take_error();
+}
+
+#[test]
+fn watch_clears_stale_error() {
+    let dir = TempDir::new("watch-error");
+
+    std::fs::write(dir.join("en.properties"), "hello=Hello!\n").unwrap();
+
+    let watcher = CatalogWatcher::new(&*dir, "en", "_text").unwrap();
+
+    std::fs::write(dir.join("de.po"), "msgstr \"\"\n").unwrap();
+
+    // give the watcher time to fail to reload the invalid file
+    thread::sleep(Duration::from_millis(500));
+
+    std::fs::remove_file(dir.join("de.po")).unwrap();
+    std::fs::write(dir.join("en.properties"), "hello=Hi!\n").unwrap();
+
+    wait_for(&watcher, "en", b"_text['hello']='Hi!';");
+
+    assert!(watcher.take_error().is_none());
+}